@@ -0,0 +1,103 @@
+//! Dense bitsets over player indices.
+//!
+//! The builders ask "which women may `m` still partner?" millions of times
+//! per run. Answering from a [`Bits`] of woman indices turns a scan over the
+//! roster into a few word operations, and intersecting two answers is one
+//! `&` per 64 players.
+
+/// A fixed-capacity set of small indices (players of one gender).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Bits {
+    words: Vec<u64>,
+}
+
+impl Bits {
+    /// The empty set, with room for indices `0..n`.
+    pub fn new(n: usize) -> Self {
+        Bits { words: vec![0; n.div_ceil(64)] }
+    }
+
+    /// The set `0..n`.
+    pub fn full(n: usize) -> Self {
+        let mut bits = Bits::new(n);
+        for i in 0..n {
+            bits.insert(i);
+        }
+        bits
+    }
+
+    pub fn insert(&mut self, i: usize) {
+        self.words[i / 64] |= 1 << (i % 64);
+    }
+
+    pub fn remove(&mut self, i: usize) {
+        self.words[i / 64] &= !(1 << (i % 64));
+    }
+
+    pub fn contains(&self, i: usize) -> bool {
+        self.words[i / 64] >> (i % 64) & 1 == 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    /// Number of members.
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Members in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &w)| {
+            let mut w = w;
+            std::iter::from_fn(move || {
+                (w != 0).then(|| {
+                    let b = w.trailing_zeros() as usize;
+                    w &= w - 1;
+                    i * 64 + b
+                })
+            })
+        })
+    }
+
+    /// Become `a ∩ b ∩ c` (all three the same capacity as `self`).
+    pub fn set_and3(&mut self, a: &Bits, b: &Bits, c: &Bits) {
+        for (((s, a), b), c) in self.words.iter_mut().zip(&a.words).zip(&b.words).zip(&c.words) {
+            *s = a & b & c;
+        }
+    }
+
+    /// Become `a ∩ b`.
+    pub fn set_and(&mut self, a: &Bits, b: &Bits) {
+        for ((s, a), b) in self.words.iter_mut().zip(&a.words).zip(&b.words) {
+            *s = a & b;
+        }
+    }
+
+    /// Become `self ∩ a ∩ b`.
+    pub fn and_with(&mut self, a: &Bits, b: &Bits) {
+        for ((s, a), b) in self.words.iter_mut().zip(&a.words).zip(&b.words) {
+            *s &= a & b;
+        }
+    }
+
+    /// `|self ∩ other|`.
+    pub fn count_and(&self, other: &Bits) -> usize {
+        self.words.iter().zip(&other.words).map(|(a, b)| (a & b).count_ones() as usize).sum()
+    }
+
+    /// `|self ∩ other ∩ {0..i}|`.
+    pub fn count_and_below(&self, other: &Bits, i: usize) -> usize {
+        let (full, part) = (i / 64, i % 64);
+        let mut n: usize = self.words[..full]
+            .iter()
+            .zip(&other.words)
+            .map(|(a, b)| (a & b).count_ones() as usize)
+            .sum();
+        if part > 0 {
+            n += (self.words[full] & other.words[full] & ((1 << part) - 1)).count_ones() as usize;
+        }
+        n
+    }
+}
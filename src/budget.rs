@@ -0,0 +1,115 @@
+//! Search budgets: when an optimizer must stop and hand back its best so far.
+//!
+//! Iteration counts mean wildly different run times across roster sizes, so
+//! the optimizers also accept a [`Budget`] — an optional wall-clock deadline
+//! plus an optional [`Cancel`] token. The inner loops (`anneal`, `court_first`,
+//! `repack`) poll [`Budget::expired`] and return the best result they have;
+//! each always completes at least one unit of work, so an expired budget still
+//! yields a valid schedule.
+//!
+//! The clock is `Instant` natively and `Date.now()` in the browser build
+//! (`wasm` feature). A wasm build without that feature has no clock, so its
+//! deadlines never expire and only cancellation stops a search.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Milliseconds on a monotonic-enough clock; only differences are meaningful.
+#[cfg(not(target_arch = "wasm32"))]
+fn now_ms() -> f64 {
+    use std::sync::OnceLock;
+    use std::time::Instant;
+    static START: OnceLock<Instant> = OnceLock::new();
+    START.get_or_init(Instant::now).elapsed().as_secs_f64() * 1000.0
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+fn now_ms() -> f64 {
+    js::now()
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+mod js {
+    use wasm_bindgen::prelude::*;
+
+    #[wasm_bindgen]
+    extern "C" {
+        #[wasm_bindgen(js_namespace = Date)]
+        pub fn now() -> f64;
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", not(feature = "wasm")))]
+fn now_ms() -> f64 {
+    0.0
+}
+
+/// A shared flag that asks a running search to stop. Clones share the flag,
+/// so one can be handed to the search and kept by whoever may cancel it.
+#[derive(Clone, Debug, Default)]
+pub struct Cancel(Arc<AtomicBool>);
+
+impl Cancel {
+    pub fn new() -> Self {
+        Cancel::default()
+    }
+
+    /// Ask every search holding this token to stop at its next check.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// When to stop: an optional deadline and an optional cancellation token.
+/// [`Budget::unlimited`] never stops on its own.
+#[derive(Clone, Debug, Default)]
+pub struct Budget {
+    /// Deadline on the [`now_ms`] clock.
+    deadline: Option<f64>,
+    cancel: Option<Cancel>,
+}
+
+impl Budget {
+    /// No deadline, no cancellation: the caller's iteration counts decide.
+    pub fn unlimited() -> Self {
+        Budget::default()
+    }
+
+    /// Stop `time` from now.
+    pub fn within(time: Duration) -> Self {
+        Budget { deadline: Some(now_ms() + time.as_secs_f64() * 1000.0), cancel: None }
+    }
+
+    /// The same budget, additionally stopped by `token`.
+    pub fn with_cancel(mut self, token: Cancel) -> Self {
+        self.cancel = Some(token);
+        self
+    }
+
+    /// Time left before the deadline, if there is one (zero once passed).
+    pub fn remaining(&self) -> Option<Duration> {
+        self.deadline
+            .map(|d| Duration::from_secs_f64(((d - now_ms()) / 1000.0).max(0.0)))
+    }
+
+    /// Past the deadline or cancelled.
+    pub fn expired(&self) -> bool {
+        self.cancel.as_ref().is_some_and(Cancel::is_cancelled)
+            || self.deadline.is_some_and(|d| now_ms() >= d)
+    }
+
+    /// A sub-budget ending after `fraction` of the time left (same token), for
+    /// dividing one budget across sequential phases. Unlimited stays unlimited.
+    pub fn portion(&self, fraction: f64) -> Self {
+        let now = now_ms();
+        Budget {
+            deadline: self.deadline.map(|d| now + (d - now).max(0.0) * fraction),
+            cancel: self.cancel.clone(),
+        }
+    }
+}
@@ -23,6 +23,7 @@
 //! Every result is checked by the crate [`verify`](crate::verify) — the same
 //! oracle used everywhere else — before being trusted.
 
+use crate::budget::Budget;
 use crate::model::{Game, Man, Roster, Round, Schedule, Team, Woman};
 use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
@@ -62,7 +63,7 @@ fn game(mi: usize, wi: usize, mj: usize, wj: usize) -> Game {
 /// same-gender load as much as this family allows.
 pub fn reflection(roster: Roster) -> Option<Schedule> {
     let n = roster.men as usize;
-    if roster.women as usize != n || n < 2 || n % 2 != 0 {
+    if roster.women as usize != n || n < 2 || !n.is_multiple_of(2) {
         return None;
     }
 
@@ -136,7 +137,6 @@ fn build_s(n: usize, order: &[usize], budget: &mut u64) -> Option<Vec<Vec<i32>>>
         idx: usize,
         cross: &[(usize, usize)],
         order: &[usize],
-        n: usize,
         s: &mut Vec<Vec<i32>>,
         row: &mut [u64],
         col: &mut [u64],
@@ -175,7 +175,7 @@ fn build_s(n: usize, order: &[usize], budget: &mut u64) -> Option<Vec<Vec<i32>>>
                 pair[u][v] = true;
                 pair[v][u] = true;
 
-                if dfs(idx + 1, cross, order, n, s, row, col, pair, budget) {
+                if dfs(idx + 1, cross, order, s, row, col, pair, budget) {
                     return true;
                 }
 
@@ -192,7 +192,7 @@ fn build_s(n: usize, order: &[usize], budget: &mut u64) -> Option<Vec<Vec<i32>>>
         false
     }
 
-    if dfs(0, &cross, order, n, &mut s, &mut row, &mut col, &mut pair, budget) {
+    if dfs(0, &cross, order, &mut s, &mut row, &mut col, &mut pair, budget) {
         Some(s)
     } else {
         None
@@ -212,7 +212,6 @@ fn build_w(n: usize, s: &[Vec<i32>], order: &[usize], budget: &mut u64) -> Optio
         idx: usize,
         cross: &[(usize, usize)],
         order: &[usize],
-        n: usize,
         s: &[Vec<i32>],
         w: &mut Vec<Vec<i32>>,
         row: &mut [u64],
@@ -244,7 +243,7 @@ fn build_w(n: usize, s: &[Vec<i32>], order: &[usize], budget: &mut u64) -> Optio
             so[sij][wv] = true;
             so[sji][wv] = true;
 
-            if dfs(idx + 1, cross, order, n, s, w, row, so, budget) {
+            if dfs(idx + 1, cross, order, s, w, row, so, budget) {
                 return true;
             }
 
@@ -258,7 +257,7 @@ fn build_w(n: usize, s: &[Vec<i32>], order: &[usize], budget: &mut u64) -> Optio
         false
     }
 
-    if dfs(0, &cross, order, n, s, &mut w, &mut row, &mut so, budget) {
+    if dfs(0, &cross, order, s, &mut w, &mut row, &mut so, budget) {
         Some(w)
     } else {
         None
@@ -296,8 +295,14 @@ fn schedule_from_table(rounds: &[&[crate::tables::G]]) -> Schedule {
 /// deterministic — otherwise a randomized-restart backtracker runs, which only
 /// scales to about `n = 10`.
 pub fn hsolssom(roster: Roster) -> Option<Schedule> {
+    hsolssom_within(roster, &Budget::unlimited())
+}
+
+/// [`hsolssom`], giving up (`None`) once `budget` expires. The budget is
+/// checked between backtracking attempts, so it may overrun by one attempt.
+pub fn hsolssom_within(roster: Roster, budget: &Budget) -> Option<Schedule> {
     let n = roster.men as usize;
-    if roster.women as usize != n || n < 10 || n % 2 != 0 || n > 64 {
+    if roster.women as usize != n || n < 10 || !n.is_multiple_of(2) || n > 64 {
         return None;
     }
 
@@ -312,10 +317,13 @@ pub fn hsolssom(roster: Roster) -> Option<Schedule> {
     // (the self-orthogonal frame gets expensive) and we return None quickly so
     // the caller falls back to `reflection`. Extending the optimum to n >= 12
     // wants cached squares or the recursive design-theory constructions.
-    let mut rng = StdRng::seed_from_u64(0x5150_7ab1_e5eed ^ n as u64);
+    let mut rng = StdRng::seed_from_u64(0x5_1507_ab1e_5eed ^ n as u64);
     let mut order: Vec<usize> = (0..n).collect();
-    let (s, w) = 'attempts: loop {
+    let (s, w) = 'attempts: {
         for _ in 0..16 {
+            if budget.expired() {
+                break;
+            }
             order.shuffle(&mut rng);
             let mut budget: u64 = 1_500_000;
             if let Some(s) = build_s(n, &order, &mut budget) {
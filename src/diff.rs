@@ -0,0 +1,192 @@
+//! Schedule comparison: what changed between two schedules for one roster.
+//!
+//! Regenerating with a different seed or emphasis may reshuffle everything or
+//! almost nothing, and the quality numbers alone don't say which. [`diff`]
+//! scores both sides with the same [`verify`] oracle and then lines them up
+//! game by game, so an organizer can judge a tweak before reprinting sheets.
+//!
+//! Games are matched by *identity* — the same four players in the same
+//! partnerships — regardless of court or which team is listed first. A game
+//! present in both schedules but in a different round is a *move*; one present
+//! in only one side is *added* or *removed*.
+
+use crate::model::{CourtPlan, Game, Player, Roster, Schedule};
+use crate::verify::{verify, Report};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// A game that appears in both schedules, but in different rounds (0-based).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Move {
+    pub game: Game,
+    pub from_round: usize,
+    pub to_round: usize,
+}
+
+/// One player's partner and opponent sets, before versus after. Only players
+/// whose sets actually changed are listed in a [`ScheduleDiff`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlayerChange {
+    pub who: Player,
+    pub partners_gained: Vec<Player>,
+    pub partners_lost: Vec<Player>,
+    pub opponents_gained: Vec<Player>,
+    pub opponents_lost: Vec<Player>,
+}
+
+/// The full comparison of schedule `before` against schedule `after`.
+#[derive(Clone, Debug)]
+pub struct ScheduleDiff {
+    /// The verifier's report for each side, for side-by-side metrics.
+    pub before: Report,
+    pub after: Report,
+    /// Games only in `after`, with their round there, in `(round, game)` order.
+    pub added: Vec<(usize, Game)>,
+    /// Games only in `before`, with their round there, in `(round, game)` order.
+    pub removed: Vec<(usize, Game)>,
+    /// Games in both, played in a different round.
+    pub moved: Vec<Move>,
+    /// Players whose set of partners or opponents differs, in roster order.
+    pub players: Vec<PlayerChange>,
+}
+
+impl ScheduleDiff {
+    /// The same games in the same rounds (courts within a round may differ).
+    pub fn is_identical(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.moved.is_empty()
+    }
+
+    /// Games kept in place: present in both schedules, in the same round.
+    pub fn unchanged(&self) -> usize {
+        self.before.games - self.removed.len() - self.moved.len()
+    }
+}
+
+/// Rounds each (canonical) game is played in, sorted. A `Vec` rather than a
+/// single index because Part 2 schedules may legitimately repeat a game.
+fn game_rounds(schedule: &Schedule) -> BTreeMap<Game, Vec<usize>> {
+    let mut at: BTreeMap<Game, Vec<usize>> = BTreeMap::new();
+    for (ri, round) in schedule.rounds.iter().enumerate() {
+        for g in &round.games {
+            at.entry(g.canonical()).or_default().push(ri);
+        }
+    }
+    at
+}
+
+/// Each player's partners and opponents across the whole schedule.
+type Contacts = BTreeMap<Player, (BTreeSet<Player>, BTreeSet<Player>)>;
+
+fn contacts(schedule: &Schedule) -> Contacts {
+    let mut c: Contacts = BTreeMap::new();
+    for g in schedule.all_games() {
+        for (us, them) in [(g.a, g.b), (g.b, g.a)] {
+            let (m, w) = (Player::M(us.man), Player::W(us.woman));
+            c.entry(m).or_default().0.insert(w);
+            c.entry(w).or_default().0.insert(m);
+            for p in [m, w] {
+                let opps = &mut c.entry(p).or_default().1;
+                opps.insert(Player::M(them.man));
+                opps.insert(Player::W(them.woman));
+            }
+        }
+    }
+    c
+}
+
+fn gained_lost(before: &BTreeSet<Player>, after: &BTreeSet<Player>) -> (Vec<Player>, Vec<Player>) {
+    (
+        after.difference(before).copied().collect(),
+        before.difference(after).copied().collect(),
+    )
+}
+
+/// Compare two schedules for the same roster and court count (or plan).
+///
+/// Within one game identity, rounds common to both sides count as unchanged;
+/// the remaining rounds are paired off in order as moves, and any surplus on
+/// one side is an addition or removal. All lists are sorted, so the result is
+/// deterministic.
+pub fn diff(
+    before: &Schedule,
+    after: &Schedule,
+    roster: Roster,
+    courts: impl Into<CourtPlan>,
+) -> ScheduleDiff {
+    let courts = &courts.into();
+    let (at_before, at_after) = (game_rounds(before), game_rounds(after));
+    let keys: BTreeSet<&Game> = at_before.keys().chain(at_after.keys()).collect();
+
+    let mut added = Vec::new();
+    let mut removed = Vec::new();
+    let mut moved = Vec::new();
+    let none = Vec::new();
+    for g in keys {
+        let rb = at_before.get(g).unwrap_or(&none);
+        let ra = at_after.get(g).unwrap_or(&none);
+        // Multiset difference of the two sorted round lists.
+        let only_b: Vec<usize> = multiset_minus(rb, ra);
+        let only_a: Vec<usize> = multiset_minus(ra, rb);
+        for (i, &from) in only_b.iter().enumerate() {
+            match only_a.get(i) {
+                Some(&to) => moved.push(Move { game: *g, from_round: from, to_round: to }),
+                None => removed.push((from, *g)),
+            }
+        }
+        for &to in only_a.iter().skip(only_b.len()) {
+            added.push((to, *g));
+        }
+    }
+    added.sort();
+    removed.sort();
+    moved.sort_by_key(|m| (m.from_round, m.to_round, m.game));
+
+    let (cb, ca) = (contacts(before), contacts(after));
+    let empty = (BTreeSet::new(), BTreeSet::new());
+    let everyone = roster
+        .men_iter()
+        .map(Player::M)
+        .chain(roster.women_iter().map(Player::W));
+    let mut players = Vec::new();
+    for who in everyone {
+        let b = cb.get(&who).unwrap_or(&empty);
+        let a = ca.get(&who).unwrap_or(&empty);
+        if a == b {
+            continue;
+        }
+        let (partners_gained, partners_lost) = gained_lost(&b.0, &a.0);
+        let (opponents_gained, opponents_lost) = gained_lost(&b.1, &a.1);
+        players.push(PlayerChange {
+            who,
+            partners_gained,
+            partners_lost,
+            opponents_gained,
+            opponents_lost,
+        });
+    }
+
+    ScheduleDiff {
+        before: verify(before, roster, courts),
+        after: verify(after, roster, courts),
+        added,
+        removed,
+        moved,
+        players,
+    }
+}
+
+/// Elements of sorted `xs` not matched one-for-one in sorted `ys`.
+fn multiset_minus(xs: &[usize], ys: &[usize]) -> Vec<usize> {
+    let mut out = Vec::new();
+    let mut j = 0;
+    for &x in xs {
+        while j < ys.len() && ys[j] < x {
+            j += 1;
+        }
+        if j < ys.len() && ys[j] == x {
+            j += 1;
+        } else {
+            out.push(x);
+        }
+    }
+    out
+}
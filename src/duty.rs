@@ -0,0 +1,111 @@
+//! Officials: a line judge or scorekeeper per court, drawn from the players
+//! sitting out.
+//!
+//! [`assign_officials`] walks a finished schedule round by round and gives
+//! each game an official from that round's byes — players in no game and no
+//! side game. Duty is shared out evenly: every round is an exact assignment
+//! (the Hungarian solver of [`rotation`](crate::rotation)) that covers as many
+//! games as the byes allow and, among those, puts duty on whoever has had the
+//! least. Nobody officiates a game with their next-round opponent in it, so
+//! no one scouts or scores the player they face next.
+//!
+//! Assign officials last: rebuilding a round's games — rotating its courts,
+//! relabeling it for a season — drops its officials.
+
+use crate::model::{Player, Roster, Round, Schedule};
+use crate::rotation::min_cost_assignment;
+use crate::verify::on_roster;
+use std::collections::HashSet;
+
+/// Cost of leaving a game without an official; any real official is cheaper.
+const UNCOVERED: i64 = 1 << 30;
+/// Cost that keeps a player off a game their next-round opponent plays in.
+const FORBIDDEN: i64 = 1 << 40;
+
+/// `schedule` with an official for each game its byes can cover, duties
+/// balanced across the schedule. A game without a free, eligible player keeps
+/// no official.
+pub fn assign_officials(schedule: &Schedule, roster: Roster) -> Schedule {
+    let mut duties = vec![0usize; roster.total_players()];
+    let everyone: Vec<Player> =
+        roster.men_iter().map(Player::M).chain(roster.women_iter().map(Player::W)).collect();
+    let free: Vec<Vec<Player>> = schedule
+        .rounds
+        .iter()
+        .map(|round| {
+            let mut busy: HashSet<Player> = round.active_players().into_iter().collect();
+            busy.extend(round.side.iter().flat_map(|s| s.players()));
+            everyone.iter().copied().filter(|p| !busy.contains(p)).collect()
+        })
+        .collect();
+    // Byes each player has left, this round's included.
+    let mut left = vec![0i64; roster.total_players()];
+    for p in free.iter().flatten() {
+        left[roster.seat(*p)] += 1;
+    }
+    let spare = schedule.rounds.len() as i64 + 1;
+    let rounds = schedule
+        .rounds
+        .iter()
+        .enumerate()
+        .map(|(r, round)| {
+            let free = &free[r];
+            let next = schedule.rounds.get(r + 1);
+            // Rows are games; columns the free players, then one "nobody" per game.
+            let cost: Vec<Vec<i64>> = round
+                .games
+                .iter()
+                .map(|g| {
+                    let on_court = g.players();
+                    let players = free.iter().map(|&p| {
+                        let facing = next.map_or(Vec::new(), |n| next_opponents(n, p));
+                        if facing.iter().any(|o| on_court.contains(o)) {
+                            FORBIDDEN
+                        } else {
+                            // Least duty first; on a tie, whoever has the fewest byes left.
+                            let seat = roster.seat(p);
+                            (2 * duties[seat] as i64 + 1) * spare + left[seat]
+                        }
+                    });
+                    players.chain(round.games.iter().map(|_| UNCOVERED)).collect()
+                })
+                .collect();
+            let officials: Vec<Option<Player>> = min_cost_assignment(&cost)
+                .into_iter()
+                .enumerate()
+                .map(|(gi, col)| (cost[gi][col] < UNCOVERED).then(|| free[col]))
+                .collect();
+            for p in officials.iter().flatten() {
+                duties[roster.seat(*p)] += 1;
+            }
+            for p in free {
+                left[roster.seat(*p)] -= 1;
+            }
+            let mut round = round.clone();
+            round.officials = officials;
+            round
+        })
+        .collect();
+    Schedule::new(rounds)
+}
+
+/// The two players `p` faces in `round`, if `p` plays in it.
+fn next_opponents(round: &Round, p: Player) -> Vec<Player> {
+    let Some(g) = round.games.iter().find(|g| g.players().contains(&p)) else {
+        return Vec::new();
+    };
+    let a = [Player::M(g.a.man), Player::W(g.a.woman)];
+    let b = [Player::M(g.b.man), Player::W(g.b.woman)];
+    if a.contains(&p) { b.to_vec() } else { a.to_vec() }
+}
+
+/// Games each player officiates in `schedule`, men then women.
+pub fn duty_counts(schedule: &Schedule, roster: Roster) -> Vec<usize> {
+    let mut duties = vec![0usize; roster.total_players()];
+    for round in &schedule.rounds {
+        for &p in round.officials.iter().flatten().filter(|&&p| on_roster(p, roster)) {
+            duties[roster.seat(p)] += 1;
+        }
+    }
+    duties
+}
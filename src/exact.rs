@@ -0,0 +1,304 @@
+//! Exact search: the best game set a small roster allows, with proof.
+//!
+//! The heuristics stop at "good"; this module can say "best". *Best* is
+//! lexicographic — most games, then fewest same-gender repeats, then fewest
+//! rounds for those games — and when a schedule [meets every
+//! bound](crate::verify::Report::meets_bounds) there is nothing to search.
+//! Otherwise a branch-and-bound over game sets settles it: partnerships are
+//! decided in order (played with some opposing team, or left out), players
+//! no game has touched yet are interchangeable so only the lowest of them is
+//! ever tried, and a branch is cut once its repeats plus the counting floor
+//! on the games still to come cannot beat the best set found. The round count
+//! is [`pack_exact`]'s to prove.
+//!
+//! Only rosters with at most [`MAX_PAIRS`] partnerships are searched, and a
+//! node cap (like the packer's work cap) keeps unlimited-budget runs deterministic.
+
+use crate::budget::Budget;
+use crate::model::{Game, Man, Roster, Round, Schedule, Team, Woman};
+use crate::objective::Objective;
+use crate::pack::pack_exact;
+use crate::rotation::assign_courts;
+use crate::search::optimize_within;
+use crate::verify::{verify, Report};
+
+/// The most partnerships (`men · women`) a roster may have to be searched.
+pub const MAX_PAIRS: usize = 64;
+
+/// Search nodes per proof before it settles for what it has.
+const MAX_NODES: u64 = 2_000_000;
+
+/// Steps for the heuristic schedule the search has to beat.
+const HEURISTIC_STEPS: u32 = 20_000;
+
+/// Whether the exact search handles this roster.
+pub fn supports(roster: Roster) -> bool {
+    roster.distinct_pairs() <= MAX_PAIRS
+}
+
+/// The best schedule for a small roster and its report, with
+/// [`optimal`](Report::optimal) set when the search proved it. Starts from
+/// the variety heuristic, then searches for anything better; when `budget` or
+/// the node cap runs out first the result is the best found, unproven.
+pub fn solve(roster: Roster, courts: u16, budget: &Budget) -> Result<(Schedule, Report), String> {
+    if !supports(roster) {
+        return Err(format!(
+            "exact search handles at most {} partnerships; {} men × {} women has {}",
+            MAX_PAIRS,
+            roster.men,
+            roster.women,
+            roster.distinct_pairs()
+        ));
+    }
+    let obj = Objective::variety();
+    let heuristic = optimize_within(roster, courts, Some(HEURISTIC_STEPS), budget, &obj, 0);
+    let report = verify(&heuristic, roster, courts);
+    let incumbent = report.is_legal().then(|| (report.games, same_gender(&report)));
+    let (better, complete) = Search::new(roster).improve(incumbent, budget);
+    let games = match better {
+        // One game a round is a valid packing; `pack_exact` tightens it.
+        Some(games) => Schedule::new(games.into_iter().map(|g| Round::new(vec![g])).collect()),
+        None => heuristic,
+    };
+    let packing = pack_exact(&games, roster, courts, budget);
+    let schedule = assign_courts(&packing.schedule, roster, courts);
+    let mut report = verify(&schedule, roster, courts);
+    report.round_floor = report.round_floor.max(packing.bound.rounds());
+    report.optimal = report.meets_bounds() || (complete && packing.is_optimal());
+    Ok((schedule, report))
+}
+
+/// `schedule`'s report, with [`optimal`](Report::optimal) proven by search
+/// when the bounds alone do not settle it: its rounds must be the fewest its
+/// games fit, and no legal game set may beat its games and repeats. Left
+/// `false` for illegal schedules, rosters past [`MAX_PAIRS`], and proofs the
+/// budget or node cap cut short.
+pub fn certify(schedule: &Schedule, roster: Roster, courts: u16, budget: &Budget) -> Report {
+    let mut report = verify(schedule, roster, courts);
+    if report.optimal || !report.is_legal() || !supports(roster) {
+        return report;
+    }
+    let packing = pack_exact(schedule, roster, courts, budget);
+    report.round_floor = report.round_floor.max(packing.bound.rounds());
+    if report.rounds > report.round_floor {
+        return report;
+    }
+    let incumbent = (report.games, same_gender(&report));
+    let (better, complete) = Search::new(roster).improve(Some(incumbent), budget);
+    report.optimal = complete && better.is_none();
+    report
+}
+
+fn same_gender(report: &Report) -> usize {
+    report.man_repeat_excess + report.woman_repeat_excess
+}
+
+/// Branch-and-bound state. Partnership `(m, w)` is bit `m · women + w` of the
+/// ledgers; a partnership is *pending* until it is played or skipped.
+struct Search {
+    roster: Roster,
+    all: u64,
+    partnered: u64,
+    skipped: u64,
+    opposed: u64,
+    /// Meetings per same-gender pair, `[a · n + b]`.
+    men_met: Vec<u8>,
+    women_met: Vec<u8>,
+    /// Same-gender pairs met at least once, and the repeats beyond that.
+    men_pairs: usize,
+    women_pairs: usize,
+    excess: usize,
+    /// Players in a game or with a skipped partnership; the rest are fresh.
+    man_seen: Vec<u16>,
+    woman_seen: Vec<u16>,
+    games: Vec<Game>,
+    /// The best complete set so far and the repeats to beat.
+    best: Option<Vec<Game>>,
+    bound: usize,
+    nodes: u64,
+}
+
+impl Search {
+    fn new(roster: Roster) -> Self {
+        let (m, w) = (roster.men as usize, roster.women as usize);
+        let pairs = roster.distinct_pairs();
+        Search {
+            roster,
+            all: if pairs == 64 { u64::MAX } else { (1 << pairs) - 1 },
+            partnered: 0,
+            skipped: 0,
+            opposed: 0,
+            men_met: vec![0; m * m],
+            women_met: vec![0; w * w],
+            men_pairs: 0,
+            women_pairs: 0,
+            excess: 0,
+            man_seen: vec![0; m],
+            woman_seen: vec![0; w],
+            games: Vec::new(),
+            best: None,
+            bound: usize::MAX,
+            nodes: 0,
+        }
+    }
+
+    fn bit(&self, m: Man, w: Woman) -> u64 {
+        1 << (m.0 as usize * self.roster.women as usize + w.0 as usize)
+    }
+
+    /// The best legal game set strictly better than `incumbent` (games, then
+    /// same-gender repeats), if any, and whether the search was exhaustive.
+    fn improve(
+        &mut self,
+        incumbent: Option<(usize, usize)>,
+        budget: &Budget,
+    ) -> (Option<Vec<Game>>, bool) {
+        let pairs = self.roster.distinct_pairs();
+        let floor = incumbent.map_or(0, |(games, _)| games);
+        for games in (floor..=self.roster.max_games()).rev() {
+            self.bound = match incumbent {
+                Some((g, excess)) if g == games => excess,
+                _ => usize::MAX,
+            };
+            let complete = self.dfs(pairs - 2 * games, budget).is_some();
+            if self.best.is_some() || !complete {
+                return (self.best.take(), complete);
+            }
+        }
+        (None, true)
+    }
+
+    /// Decide the lowest pending partnership, with `skips` more partnerships
+    /// left to leave out. `None` once the budget or node cap runs out.
+    fn dfs(&mut self, skips: usize, budget: &Budget) -> Option<()> {
+        self.nodes += 1;
+        if self.nodes > MAX_NODES || (self.nodes.is_multiple_of(1024) && budget.expired()) {
+            return None;
+        }
+        let pending = self.all & !(self.partnered | self.skipped);
+        if pending == 0 {
+            if self.excess < self.bound {
+                self.bound = self.excess;
+                self.best = Some(self.games.clone());
+            }
+            return Some(());
+        }
+        // Games still to come, and the repeats they must add at the least.
+        let left = (pending.count_ones() as usize - skips) / 2;
+        let floor = left.saturating_sub(self.roster.distinct_man_pairs() - self.men_pairs)
+            + left.saturating_sub(self.roster.distinct_woman_pairs() - self.women_pairs);
+        let mixed_free = (self.all & !self.opposed).count_ones() as usize;
+        if self.excess + floor >= self.bound || mixed_free < 2 * left {
+            return Some(());
+        }
+        let p = pending.trailing_zeros() as usize;
+        let women = self.roster.women as usize;
+        let (m, w) = (Man((p / women) as u16), Woman((p % women) as u16));
+        let mut options = self.options(m, w, pending);
+        options.sort_by_key(|&(added, _)| added);
+        for (_, game) in options {
+            self.play(game, 1);
+            let done = self.dfs(skips, budget);
+            self.play(game, -1);
+            done?;
+        }
+        if skips > 0 {
+            self.skip(m, w, 1);
+            let done = self.dfs(skips - 1, budget);
+            self.skip(m, w, -1);
+            done?;
+        }
+        Some(())
+    }
+
+    /// Every game partnering `(m, w)` that the ledgers allow, with the repeats
+    /// each adds. Of the fresh players on each side only the lowest is tried.
+    fn options(&self, m: Man, w: Woman, pending: u64) -> Vec<(usize, Game)> {
+        let fresh_man =
+            self.roster.men_iter().find(|&x| x != m && self.man_seen[x.0 as usize] == 0);
+        let fresh_woman =
+            self.roster.women_iter().find(|&x| x != w && self.woman_seen[x.0 as usize] == 0);
+        let mut out = Vec::new();
+        for m2 in self.roster.men_iter().filter(|&x| x != m) {
+            if self.man_seen[m2.0 as usize] == 0 && Some(m2) != fresh_man {
+                continue;
+            }
+            for w2 in self.roster.women_iter().filter(|&x| x != w) {
+                if self.woman_seen[w2.0 as usize] == 0 && Some(w2) != fresh_woman {
+                    continue;
+                }
+                let mixed = self.bit(m, w2) | self.bit(m2, w);
+                if pending & self.bit(m2, w2) == 0 || self.opposed & mixed != 0 {
+                    continue;
+                }
+                let game = Game::new(Team::new(m, w), Team::new(m2, w2));
+                let added = (self.men_met[self.man_key(m, m2)] > 0) as usize
+                    + (self.women_met[self.woman_key(w, w2)] > 0) as usize;
+                out.push((added, game));
+            }
+        }
+        out
+    }
+
+    fn man_key(&self, a: Man, b: Man) -> usize {
+        let (a, b) = (a.0.min(b.0) as usize, a.0.max(b.0) as usize);
+        a * self.roster.men as usize + b
+    }
+
+    fn woman_key(&self, a: Woman, b: Woman) -> usize {
+        let (a, b) = (a.0.min(b.0) as usize, a.0.max(b.0) as usize);
+        a * self.roster.women as usize + b
+    }
+
+    /// Add (`dir = 1`) or remove (`dir = -1`) `game` and its ledger entries.
+    fn play(&mut self, game: Game, dir: i8) {
+        let partners = self.bit(game.a.man, game.a.woman) | self.bit(game.b.man, game.b.woman);
+        let opps = game.mixed_opps().iter().fold(0, |acc, &(m, w)| acc | self.bit(m, w));
+        self.partnered ^= partners;
+        self.opposed ^= opps;
+        let mk = self.man_key(game.a.man, game.b.man);
+        let wk = self.woman_key(game.a.woman, game.b.woman);
+        for (met, key, pairs) in [
+            (&mut self.men_met, mk, &mut self.men_pairs),
+            (&mut self.women_met, wk, &mut self.women_pairs),
+        ] {
+            if dir > 0 {
+                met[key] += 1;
+                if met[key] == 1 {
+                    *pairs += 1;
+                } else {
+                    self.excess += 1;
+                }
+            } else {
+                if met[key] == 1 {
+                    *pairs -= 1;
+                } else {
+                    self.excess -= 1;
+                }
+                met[key] -= 1;
+            }
+        }
+        for m in [game.a.man, game.b.man] {
+            see(&mut self.man_seen[m.0 as usize], dir);
+        }
+        for w in [game.a.woman, game.b.woman] {
+            see(&mut self.woman_seen[w.0 as usize], dir);
+        }
+        if dir > 0 {
+            self.games.push(game);
+        } else {
+            self.games.pop();
+        }
+    }
+
+    /// Leave partnership `(m, w)` out (`dir = 1`), or take that back.
+    fn skip(&mut self, m: Man, w: Woman, dir: i8) {
+        self.skipped ^= self.bit(m, w);
+        see(&mut self.man_seen[m.0 as usize], dir);
+        see(&mut self.woman_seen[w.0 as usize], dir);
+    }
+}
+
+fn see(seen: &mut u16, dir: i8) {
+    *seen = seen.wrapping_add_signed(dir.into());
+}
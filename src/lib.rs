@@ -4,11 +4,27 @@
 //! The pipeline is: construct a schedule (currently [`greedy`]), then
 //! [`verify`] it against those bounds, then [`report`] it.
 
+pub mod bits;
+pub mod budget;
 pub mod construct;
+pub mod diff;
+pub mod duty;
+pub mod exact;
 pub mod greedy;
 pub mod model;
+pub mod objective;
+pub mod pack;
+pub mod parallel;
+mod pick;
+pub mod pins;
+pub mod quota;
 pub mod report;
+pub mod rotation;
+pub mod rules;
 pub mod search;
+pub mod season;
+pub mod session;
+pub mod side;
 pub mod tables;
 pub mod target;
 pub mod verify;
@@ -17,7 +33,8 @@ pub mod wasm;
 #[cfg(test)]
 mod tests {
     use crate::greedy::greedy;
-    use crate::model::{Game, Man, Roster, Round, Schedule, Team, Woman};
+    use crate::model::{Game, Man, Player, Roster, Round, Schedule, Team, Woman};
+    use crate::objective::Objective;
     use crate::verify::{verify, Violation};
 
     fn game(m1: u16, w1: u16, m2: u16, w2: u16) -> Game {
@@ -50,6 +67,98 @@ mod tests {
             .any(|v| matches!(v, Violation::RepeatedPartnership { .. })));
     }
 
+    #[test]
+    fn violations_carry_slots_in_fixed_order() {
+        use crate::verify::Slot;
+        let at = |round, court| Slot { round, court };
+        // Round 2 repeats round 1's first game and double-books M0.
+        let s = Schedule::new(vec![
+            Round::new(vec![game(0, 0, 1, 1), game(2, 2, 3, 3)]),
+            Round::new(vec![game(3, 3, 2, 2), game(0, 0, 1, 1), game(0, 2, 3, 1)]),
+        ]);
+        let r = verify(&s, Roster::new(4, 4), 3);
+        assert_eq!(
+            r.violations[0],
+            Violation::DoubleBooked { who: Player::M(Man(0)), at: vec![at(1, 1), at(1, 2)] }
+        );
+        assert_eq!(
+            r.violations.iter().find(|v| matches!(v, Violation::RepeatedPartnership { .. })),
+            Some(&Violation::RepeatedPartnership {
+                man: Man(0),
+                woman: Woman(0),
+                at: vec![at(0, 0), at(1, 1)],
+            })
+        );
+        assert_eq!(
+            r.violations[1].to_string(),
+            "M4 is on 2 courts at once: round 2 court 1, round 2 court 3"
+        );
+        // Same input, same order — no hash-map iteration leaks through.
+        assert_eq!(r.violations, verify(&s, Roster::new(4, 4), 3).violations);
+    }
+
+    #[test]
+    fn rules_can_be_dropped_and_added() {
+        use crate::rules::{builtin, Rule};
+        use crate::verify::{verify_with, Report, Slot};
+
+        /// Club rule: nobody plays more than `max` rounds in a row; scores the
+        /// longest streak.
+        struct Rest {
+            max: usize,
+        }
+        impl Rule for Rest {
+            fn name(&self) -> &'static str {
+                "rest"
+            }
+            fn apply(&self, s: &Schedule, report: &mut Report) {
+                let mut longest = 0;
+                for m in report.roster.men_iter() {
+                    let mut run = Vec::new();
+                    for (ri, round) in s.rounds.iter().enumerate() {
+                        match round.games.iter().position(|g| g.a.man == m || g.b.man == m) {
+                            Some(gi) => run.push(Slot { round: ri, court: gi }),
+                            None => run.clear(),
+                        }
+                        longest = longest.max(run.len());
+                        if run.len() == self.max + 1 {
+                            report.violations.push(Violation::Rule {
+                                rule: self.name(),
+                                message: format!("{} plays {} rounds running", m, run.len()),
+                                at: run.clone(),
+                            });
+                        }
+                    }
+                }
+                report.rule_scores.push(("rest.longest".into(), longest as f64));
+            }
+        }
+
+        // M0 and M1 play all three rounds; the third repeats round 1's game.
+        let g = game(0, 0, 1, 1);
+        let s = Schedule::new(vec![
+            Round::new(vec![g]),
+            Round::new(vec![game(0, 1, 1, 0)]),
+            Round::new(vec![g]),
+        ]);
+        let roster = Roster::new(2, 2);
+
+        // Without the once-rules only the club rule speaks.
+        let mut rules: Vec<Box<dyn Rule>> = builtin()
+            .into_iter()
+            .filter(|r| r.name() == "structure")
+            .collect();
+        rules.push(Box::new(Rest { max: 2 }));
+        let r = verify_with(&s, roster, 1, &rules);
+        assert_eq!(r.violations.len(), 2, "{:?}", r.violations);
+        assert!(r.violations.iter().all(|v| matches!(v, Violation::Rule { rule: "rest", .. })));
+        assert_eq!(r.violations[0].slots().len(), 3);
+        assert_eq!(r.rule_scores, vec![("rest.longest".to_string(), 3.0)]);
+        // Measurements don't depend on the rules chosen.
+        assert_eq!(r.partner_repeat_excess, verify(&s, roster, 1).partner_repeat_excess);
+        assert!(!verify(&s, roster, 1).is_legal());
+    }
+
     #[test]
     fn verifier_flags_double_booking() {
         // M0 plays on two courts in the same round.
@@ -74,6 +183,370 @@ mod tests {
             .any(|v| matches!(v, Violation::Malformed { .. })));
     }
 
+    #[test]
+    fn diff_matches_games_across_courts_and_rounds() {
+        use crate::diff::diff;
+        let roster = Roster::new(4, 4);
+        let before = Schedule::new(vec![
+            Round::new(vec![game(0, 0, 1, 1), game(2, 2, 3, 3)]),
+            Round::new(vec![game(0, 1, 2, 3)]),
+        ]);
+        // Same first round with courts and teams swapped; round-2 game moved
+        // to round 3; one new game in round 2.
+        let after = Schedule::new(vec![
+            Round::new(vec![game(3, 3, 2, 2), game(1, 1, 0, 0)]),
+            Round::new(vec![game(1, 0, 3, 2)]),
+            Round::new(vec![game(2, 3, 0, 1)]),
+        ]);
+        let d = diff(&before, &after, roster, 2);
+        assert_eq!(d.unchanged(), 2);
+        assert!(d.removed.is_empty());
+        assert_eq!(d.added, vec![(1, game(1, 0, 3, 2).canonical())]);
+        assert_eq!(d.moved.len(), 1);
+        assert_eq!((d.moved[0].from_round, d.moved[0].to_round), (1, 2));
+        // Everyone in the new game gained contacts; nobody else changed.
+        assert_eq!(d.players.len(), 4);
+        assert_eq!(d.after.games, 4);
+        assert!(diff(&before, &before, roster, 2).is_identical());
+    }
+
+    #[test]
+    fn live_report_matches_verify_through_adds_and_removes() {
+        use crate::target::by_games_per_player;
+        use crate::verify::LiveReport;
+        let check = |s: &Schedule, live: &LiveReport, roster, courts| {
+            let r = verify(s, roster, courts);
+            assert_eq!(live.games(), r.games);
+            assert_eq!(live.rounds(), r.rounds);
+            assert_eq!(live.is_legal(), r.is_legal());
+            assert_eq!(live.partner_repeat_excess(), r.partner_repeat_excess);
+            assert_eq!(live.mixed_repeat_excess(), r.mixed_repeat_excess);
+            assert_eq!(live.man_repeat_excess(), r.man_repeat_excess);
+            assert_eq!(live.man_max_meetings(), r.man_max_meetings);
+            assert_eq!(live.woman_repeat_excess(), r.woman_repeat_excess);
+            assert_eq!(live.woman_max_meetings(), r.woman_max_meetings);
+            assert_eq!(live.participation_spread(), r.participation_spread());
+            assert_eq!(live.court_utilization(), r.court_utilization);
+            let roster: Roster = roster;
+            for m in roster.men_iter() {
+                for w in roster.women_iter() {
+                    let i = w.0 as usize;
+                    assert_eq!(live.unpartnered(m).contains(i), live.partnered(m, w) == 0);
+                    assert_eq!(live.unopposed(m).contains(i), live.opposed(m, w) == 0);
+                }
+            }
+        };
+        // Part 2 above the ceiling exercises every ledger's repeat tracking.
+        for (m, w, c, each) in [(6, 6, 3, 8), (5, 7, 2, 6), (4, 4, 2, 3)] {
+            let roster = Roster::new(m, w);
+            let mut s = by_games_per_player(roster, c, each, &Objective::default(), 5);
+            let mut live = LiveReport::of(&s, roster, c);
+            check(&s, &live, roster, c);
+            // Peel rounds off the front (max/min must fall back correctly).
+            while !s.rounds.is_empty() {
+                let round = s.rounds.remove(0);
+                live.remove_round(&round.games);
+                check(&s, &live, roster, c);
+            }
+        }
+    }
+
+    #[test]
+    fn forty_a_side_builds_quickly_with_fair_byes() {
+        use crate::target::by_games_per_player;
+        use std::time::{Duration, Instant};
+        // The browser cap: 40 a side. Generous slack for slow CI.
+        let roster = Roster::new(40, 40);
+        let start = Instant::now();
+        let s = by_games_per_player(roster, 20, 6, &Objective::default(), 1);
+        assert!(start.elapsed() < Duration::from_secs(20), "took {:?}", start.elapsed());
+        let r = verify(&s, roster, 20);
+        assert!(r.is_legal(), "{:?}", r.violations);
+        assert_eq!(r.games, 120);
+        assert_eq!(r.participation_spread(), 0);
+    }
+
+    #[test]
+    fn budgets_stop_the_search_with_a_valid_schedule() {
+        use crate::budget::{Budget, Cancel};
+        use crate::search::optimize_within;
+        use std::time::{Duration, Instant};
+        let roster = Roster::new(12, 12);
+
+        // A short deadline is honoured (with generous slack for slow CI).
+        let start = Instant::now();
+        let budget = Budget::within(Duration::from_millis(300));
+        let s = optimize_within(roster, 6, None, &budget, &Objective::balanced(), 1);
+        assert!(start.elapsed() < Duration::from_secs(5), "took {:?}", start.elapsed());
+        let r = verify(&s, roster, 6);
+        assert!(r.is_legal() && r.games > 0);
+
+        // Cancelled before it starts: still one unit of work, still legal.
+        let token = Cancel::new();
+        token.cancel();
+        let budget = Budget::unlimited().with_cancel(token);
+        let s = optimize_within(roster, 6, Some(1_000_000), &budget, &Objective::balanced(), 1);
+        let r = verify(&s, roster, 6);
+        assert!(r.is_legal() && r.games > 0);
+    }
+
+    #[test]
+    fn results_do_not_depend_on_thread_count() {
+        use crate::parallel::with_threads;
+        use crate::search::optimize;
+        use crate::target::by_total_games;
+        let roster = Roster::new(7, 6);
+        let run = |threads| {
+            with_threads(threads, || {
+                let s = optimize(roster, 3, 3_000, &Objective::balanced(), 11);
+                let t = by_total_games(roster, 3, 15, &Objective::default(), 11);
+                (s, t)
+            })
+        };
+        assert_eq!(run(1), run(4));
+    }
+
+    #[test]
+    fn stepped_optimizer_reports_progress_and_matches_optimize() {
+        use crate::budget::Budget;
+        use crate::search::{optimize, optimize_with_progress, Optimizer};
+        use std::sync::Mutex;
+        use std::time::Duration;
+        let roster = Roster::new(7, 5);
+
+        let mut opt = Optimizer::new(roster, 2, 3_000, &Objective::balanced(), 4);
+        assert!(!opt.step(&Budget::unlimited()), "first step only constructs");
+        let mut slices = 1;
+        while !opt.step(&Budget::within(Duration::from_millis(5))) {
+            let best = opt.best();
+            assert!(verify(&best, roster, 2).is_legal(), "interim results are legal");
+            slices += 1;
+        }
+        assert!(slices > 2, "small slices should take several steps");
+        let p = opt.progress().expect("starts reported progress");
+        assert_eq!((p.start, p.starts, p.step, p.steps), (5, 6, 500, Some(500)));
+        assert_eq!(opt.best(), optimize(roster, 2, 3_000, &Objective::balanced(), 4));
+
+        let seen = Mutex::new(Vec::new());
+        let report = |p: &crate::search::Progress| seen.lock().unwrap().push((p.start, p.step));
+        optimize_with_progress(roster, 2, Some(3_000), &Budget::unlimited(), &Objective::balanced(), 4, &report);
+        let mut seen = seen.into_inner().unwrap();
+        seen.sort();
+        assert_eq!(seen.first(), Some(&(0, 256)));
+        assert_eq!(seen.last(), Some(&(5, 500)));
+    }
+
+    #[test]
+    fn frontier_lists_every_non_dominated_trade_off() {
+        use crate::budget::Budget;
+        use crate::search::{optimize, optimize_frontier};
+        let roster = Roster::new(6, 6);
+        let options = optimize_frontier(roster, 3, Some(12_000), &Budget::unlimited(), &Objective::balanced(), 3);
+        assert!(options.len() >= 2, "6×6 trades courts for variety");
+        let key = |r: &crate::verify::Report| {
+            (r.rounds, r.man_repeat_excess + r.woman_repeat_excess, r.participation_spread())
+        };
+        for (s, r) in &options {
+            assert!(r.is_legal() && r.games == 18);
+            assert_eq!(verify(s, roster, 3).rounds, r.rounds, "report matches its schedule");
+        }
+        for (i, (_, a)) in options.iter().enumerate() {
+            for (_, b) in &options[i + 1..] {
+                let (ka, kb) = (key(a), key(b));
+                assert!(ka.0 < kb.0, "sorted courts end first, no duplicates");
+                assert!(ka.1 > kb.1 || ka.2 > kb.2, "{ka:?} would dominate {kb:?}");
+            }
+        }
+        // Both emphasis corners are on the menu.
+        for obj in [Objective::courts(), Objective::variety()] {
+            let pick = verify(&optimize(roster, 3, 12_000, &obj, 3), roster, 3);
+            assert!(options.iter().any(|(_, r)| key(r) == key(&pick)));
+        }
+    }
+
+    #[test]
+    fn objective_weights_parse_and_steer_the_search() {
+        use crate::search::optimize;
+        let obj = Objective::balanced().with_overrides("excess:20, same_gender:2,").unwrap();
+        assert_eq!((obj.excess, obj.same_gender, obj.spread), (20.0, 2.0, 1.0));
+        assert!(Objective::default().with_overrides("tempo:1").is_err());
+        assert!(Objective::default().with_overrides("excess").is_err());
+        assert!(Objective::default().with_overrides("excess:-1").is_err());
+
+        // The emphasis presets are just round weights.
+        let roster = Roster::new(6, 6);
+        let variety = Objective::balanced().with_overrides("rounds:0").unwrap();
+        assert_eq!(
+            optimize(roster, 3, 6_000, &variety, 2),
+            optimize(roster, 3, 6_000, &Objective::variety(), 2)
+        );
+    }
+
+    #[test]
+    fn warm_starts_keep_most_of_a_valid_schedule() {
+        use crate::budget::Budget;
+        use crate::diff::diff;
+        use crate::search::{optimize, optimize_from};
+        use crate::target::by_total_games_from;
+        let roster = Roster::new(7, 5);
+        let obj = Objective::variety();
+        let last_week = optimize(roster, 2, 60, &obj, 1);
+        let before = verify(&last_week, roster, 2);
+
+        let unlimited = Budget::unlimited();
+        let from = |s: &Schedule, courts: u16, obj: &Objective| {
+            optimize_from(s, roster, courts, Some(6_000), &unlimited, obj, 2)
+        };
+
+        let better = from(&last_week, 2, &obj).expect("a legal starting schedule");
+        let after = verify(&better, roster, 2);
+        assert!(after.is_legal());
+        assert_eq!(after.games, before.games);
+        let excess = |r: &crate::verify::Report| r.man_repeat_excess + r.woman_repeat_excess;
+        assert!(excess(&after) < excess(&before));
+        assert!(diff(&last_week, &better, roster, 2).unchanged() * 3 >= before.games);
+
+        // A high churn weight keeps the schedule as it is.
+        let stay = obj.with_overrides("churn:1000").unwrap();
+        let same = from(&last_week, 2, &stay).unwrap();
+        assert!(diff(&last_week, &same, roster, 2).is_identical());
+
+        // Two more courts: still legal, no fewer games.
+        let wider = verify(&from(&last_week, 4, &obj).expect("fits on more courts"), roster, 4);
+        assert!(wider.is_legal() && wider.games >= before.games);
+
+        // Invalid starting points are rejected, not repaired.
+        let mut twice = last_week.clone();
+        twice.rounds.push(twice.rounds[0].clone());
+        assert!(from(&twice, 2, &obj).is_err());
+        assert!(from(&last_week, 1, &obj).is_err());
+
+        // Part 2 keeps the starting rounds and builds the rest after them.
+        let start = Schedule::new(last_week.rounds[..2].to_vec());
+        let topped = by_total_games_from(&start, roster, 2, 10, &obj, 3).unwrap();
+        assert_eq!(topped.num_games(), 10);
+        assert_eq!(topped.rounds[..2], start.rounds[..]);
+    }
+
+    #[test]
+    fn pinned_games_and_locked_rounds_survive_optimization() {
+        use crate::budget::Budget;
+        use crate::pins::Pins;
+        use crate::rotation::assign_courts_pinned;
+        use crate::search::{optimize, optimize_pinned};
+        use crate::verify::verify_with;
+        let roster = Roster::new(7, 5);
+        let obj = Objective::balanced();
+        let printed = Round::with_courts(vec![game(2, 2, 3, 3), game(4, 4, 5, 0)], vec![1, 0]);
+        let pins = Pins::new()
+            .game(game(0, 0, 1, 1), 0, Some(1))
+            .round(2, printed)
+            .game(game(6, 2, 0, 3), 4, None);
+        let mut rules = crate::rules::builtin();
+        rules.push(Box::new(pins.clone()));
+
+        let unlimited = Budget::unlimited();
+        let pinned = optimize_pinned(roster, 2, Some(4_000), &unlimited, &obj, &pins, 3).unwrap();
+        let report = verify_with(&pinned, roster, 2, &rules);
+        assert!(report.is_legal(), "{:?}", report.violations);
+        let free = verify(&optimize(roster, 2, 4_000, &obj, 3), roster, 2);
+        assert_eq!((report.games, report.rounds), (free.games, free.rounds));
+
+        // Court rotation keeps pinned courts; a schedule built without the
+        // pins breaks them.
+        let rotated = assign_courts_pinned(&pinned, roster, 2, &pins);
+        assert!(verify_with(&rotated, roster, 2, &rules).is_legal());
+        let plain = optimize(roster, 2, 4_000, &obj, 3);
+        let broken = verify_with(&plain, roster, 2, &rules);
+        assert!(broken.violations.iter().any(|v| matches!(v, Violation::Rule { rule: "pins", .. })));
+
+        // Pins that clash among themselves are refused up front.
+        let clash = Pins::new().game(game(0, 0, 1, 1), 0, None).game(game(0, 2, 3, 3), 0, None);
+        assert!(optimize_pinned(roster, 2, Some(100), &unlimited, &obj, &clash, 1).is_err());
+    }
+
+    #[test]
+    fn exact_repack_finds_the_fewest_rounds_and_proves_it() {
+        use crate::budget::Budget;
+        use crate::pack::{pack_exact, RoundBound};
+        use crate::search::optimize;
+        let unlimited = Budget::unlimited();
+
+        // A triangle of games, each sharing a man with the next: the counting
+        // bounds say 2 rounds, but only an exhaustive search proves 3.
+        let roster = Roster::new(4, 6);
+        let tri = [game(0, 0, 1, 1), game(1, 2, 2, 3), game(2, 4, 0, 5)];
+        let spread = Schedule::new(tri.iter().map(|&g| Round::new(vec![g])).collect());
+        assert_eq!(verify(&spread, roster, 2).round_floor, 2);
+        let p = pack_exact(&spread, roster, 2, &unlimited);
+        assert_eq!(p.bound, RoundBound::Exhausted { rounds: 3 });
+        assert!(p.is_optimal());
+
+        // A full game set scattered one game per round packs back down to
+        // the capacity floor.
+        let roster = Roster::new(7, 5);
+        let best = optimize(roster, 2, 2_000, &Objective::courts(), 1);
+        let scattered: Vec<Round> = best.all_games().map(|&g| Round::new(vec![g])).collect();
+        let p = pack_exact(&Schedule::new(scattered), roster, 2, &unlimited);
+        let r = verify(&p.schedule, roster, 2);
+        assert!(r.is_legal() && p.is_optimal());
+        assert_eq!((r.games, r.rounds), (17, 9));
+        assert_eq!(p.bound, RoundBound::Capacity { games: 17, per_round: 2 });
+    }
+
+    #[test]
+    fn exact_search_proves_small_rosters_optimal() {
+        use crate::budget::Budget;
+        use crate::exact::{certify, solve};
+        use crate::search::optimize;
+        // 3×5: the counting floor (4+0 same-gender repeats) is out of reach;
+        // the search proves 5 is the best any 7-game schedule can do.
+        let roster = Roster::new(3, 5);
+        let (s, r) = solve(roster, 1, &Budget::unlimited()).unwrap();
+        assert!(r.is_legal() && r.optimal && !r.meets_bounds());
+        assert_eq!((r.games, r.man_repeat_excess + r.woman_repeat_excess), (7, 5));
+        assert!(certify(&s, roster, 1, &Budget::unlimited()).optimal);
+
+        // Full courts on 5×5 cost same-gender repeats, so the courts pick is
+        // legal but beaten; the exact pick is proven.
+        let roster = Roster::new(5, 5);
+        let courts = optimize(roster, 2, 20_000, &Objective::courts(), 1);
+        assert!(verify(&courts, roster, 2).is_legal());
+        assert!(!certify(&courts, roster, 2, &Budget::unlimited()).optimal);
+        assert!(solve(roster, 2, &Budget::unlimited()).unwrap().1.optimal);
+        assert!(solve(Roster::new(9, 9), 4, &Budget::unlimited()).is_err());
+    }
+
+    #[test]
+    fn court_rotation_evens_out_courts_without_touching_games() {
+        use crate::construct::reflection;
+        use crate::rotation::assign_courts;
+        // 6×6 reflection can't beat 2 (checked exhaustively); 8 and 10 rotate
+        // perfectly.
+        for (n, best) in [(6u16, 2), (8, 0), (10, 0), (14, 2)] {
+            let roster = Roster::new(n, n);
+            let s = reflection(roster).unwrap();
+            let rotated = assign_courts(&s, roster, n / 2);
+            let before = verify(&s, roster, n / 2);
+            let after = verify(&rotated, roster, n / 2);
+            assert!(after.is_legal(), "{:?}", after.violations);
+            assert!(after.court_imbalance() <= best, "n={}: {}", n, after.court_imbalance());
+            assert!(after.court_imbalance() < before.court_imbalance());
+            for (a, b) in s.rounds.iter().zip(&rotated.rounds) {
+                let mut a: Vec<Game> = a.games.iter().map(Game::canonical).collect();
+                let mut b: Vec<Game> = b.games.iter().map(Game::canonical).collect();
+                a.sort();
+                b.sort();
+                assert_eq!(a, b, "rotation changed a round's games");
+            }
+        }
+        // Two games on one court is a structural violation.
+        let clash = Round::with_courts(vec![game(0, 0, 1, 1), game(2, 2, 3, 3)], vec![1, 1]);
+        let r = verify(&Schedule::new(vec![clash]), Roster::new(4, 4), 2);
+        assert!(!r.is_structurally_valid());
+        assert!(r.violations.iter().any(|v| matches!(v, Violation::CourtClash { .. })));
+    }
+
     #[test]
     fn greedy_is_always_legal() {
         for (m, w, c) in [(6, 6, 3), (8, 8, 4), (5, 7, 2), (4, 6, 3), (10, 10, 5)] {
@@ -98,10 +571,10 @@ mod tests {
 
     #[test]
     fn optimize_is_legal_and_near_ceiling() {
-        use crate::search::{optimize, EMPHASIS_BALANCED};
+        use crate::search::optimize;
         for (m, w, c) in [(6, 6, 3), (8, 8, 4), (5, 7, 2), (10, 10, 5)] {
             let roster = Roster::new(m, w);
-            let s = optimize(roster, c, 24_000, EMPHASIS_BALANCED, 1);
+            let s = optimize(roster, c, 24_000, &Objective::balanced(), 1);
             let r = verify(&s, roster, c);
             assert!(r.is_legal(), "illegal for {}x{}: {:?}", m, w, r.violations);
             // The heuristic reaches, or comes within one of, the game ceiling.
@@ -120,9 +593,9 @@ mod tests {
     fn variety_emphasis_drives_same_gender_low_on_6x6() {
         // Floor is 3+3=6. The raw greedy sits around 15. Variety emphasis frees
         // the search to approach the floor (at some court-utilization cost).
-        use crate::search::{optimize, EMPHASIS_VARIETY};
+        use crate::search::optimize;
         let roster = Roster::new(6, 6);
-        let s = optimize(roster, 3, 60_000, EMPHASIS_VARIETY, 3);
+        let s = optimize(roster, 3, 60_000, &Objective::variety(), 3);
         let r = verify(&s, roster, 3);
         assert!(r.is_legal());
         assert_eq!(r.games, 18);
@@ -189,9 +662,9 @@ mod tests {
 
     #[test]
     fn optimize_returns_optimal_construction_on_10x10() {
-        use crate::search::{optimize, EMPHASIS_BALANCED};
+        use crate::search::optimize;
         let roster = Roster::new(10, 10);
-        let s = optimize(roster, 5, 5_000, EMPHASIS_BALANCED, 1);
+        let s = optimize(roster, 5, 5_000, &Objective::balanced(), 1);
         let r = verify(&s, roster, 5);
         // The optimizer should short-circuit to the optimal construction.
         assert!(r.is_legal());
@@ -205,17 +678,162 @@ mod tests {
     fn wasm_json_has_expected_shape() {
         use crate::wasm::generate_json;
         // Part 1 balanced, 6×6/3 → 18 games, legal, well-formed JSON.
-        let j = generate_json(6, 6, 3, 1, 0, 7);
+        let j = generate_json(6, 6, 3, 1, 0, 7, 0, "");
         assert!(j.contains("\"games\":18"), "{}", j);
         assert!(j.contains("\"maxGames\":18"));
         assert!(j.contains("\"rounds\":[["));
         assert!(j.contains("\"legal\":true"));
         assert!(j.contains("\"gamesPerMan\":["));
         // Part 2 each=8 (above ceiling) → forced partner repeats at floor 12.
-        let j2 = generate_json(6, 6, 3, 3, 8, 1);
+        let j2 = generate_json(6, 6, 3, 3, 8, 1, 0, "");
         assert!(j2.contains("\"partnerExcess\":12"), "{}", j2);
+        // Part 2 rounds=4 → 4 full rounds of 3.
+        let j3 = generate_json(7, 6, 3, 6, 4, 1, 0, "");
+        assert!(j3.contains("\"games\":12,\"maxGames\":21,\"rounds\":4"), "{}", j3);
         // Oversized input is rejected, not run.
-        assert!(generate_json(40, 40, 5, 1, 0, 1).contains("\"error\""));
+        assert!(generate_json(41, 40, 5, 1, 0, 1, 0, "").contains("\"error\""));
+        assert!(generate_json(6, 6, 3, 5, 0, 1, 0, "").starts_with("{\"options\":[{\"men\":6"));
+    }
+
+    #[test]
+    fn session_fills_the_rounds_that_fit() {
+        use crate::session::{clock, parse_clock, Session};
+        use crate::target::by_session;
+        // 7–9pm, 12-minute games, 2-minute changeovers: 8 rounds, ten
+        // minutes to spare. A 10-minute break at 8pm fits in the slack; a
+        // 15-minute one costs a round.
+        let evening = Session::new(120, 12).starting_at(parse_clock("19:00").unwrap()).changeover(2);
+        assert_eq!(evening.rounds(), 8);
+        assert_eq!(evening.round_times()[7], (98, 110));
+        let rest = evening.with_break(60, 10);
+        assert_eq!(rest.rounds(), 8);
+        assert_eq!(rest.break_before(), Some(5));
+        assert_eq!(clock(&rest, rest.round_times()[5].0), "20:20");
+        assert_eq!(clock(&rest, rest.round_times()[7].1), "21:00");
+        assert_eq!(evening.with_break(60, 15).rounds(), 7);
+        assert_eq!(Session::new(10, 12).rounds(), 0);
+        assert!(parse_clock("25:00").is_err());
+
+        // Every court busy in every round; repeats are avoided before byes
+        // are evened out, so near the ceiling the spread can reach two.
+        let roster = Roster::new(10, 9);
+        let s = by_session(roster, 4, &rest, &Objective::default(), 1);
+        let r = verify(&s, roster, 4);
+        assert!(r.is_legal(), "{:?}", r.violations);
+        assert_eq!((r.rounds, r.games), (8, 32));
+        assert!(r.participation_spread() <= 2);
+    }
+
+    #[test]
+    fn season_fit_spreads_repeats_across_nights() {
+        use crate::season::Season;
+        use crate::target::by_games_per_player;
+        // Four league nights of two games each on 10×10: every man could
+        // still have a new partner each game, but nights built alone reuse
+        // last week's pairs. Relabeling keeps each night as good as built.
+        let roster = Roster::new(10, 10);
+        let obj = Objective::default();
+        let (mut plain, mut fitted) = (Season::new(roster), Season::new(roster));
+        for week in 1..=4 {
+            let night = by_games_per_player(roster, 5, 2, &obj, week);
+            let fit = fitted.fit(&night, &obj, week);
+            let (a, b) = (verify(&night, roster, 5), verify(&fit, roster, 5));
+            assert!(b.is_legal());
+            assert_eq!((a.games, a.rounds), (b.games, b.rounds));
+            assert_eq!(a.man_repeat_excess, b.man_repeat_excess);
+            plain.record(&night);
+            fitted.record(&fit);
+        }
+        let (p, f) = (plain.report(), fitted.report());
+        assert_eq!(f.nights, 4);
+        assert!(f.games_per_man.iter().all(|&g| g == 8));
+        assert!(f.partner_repeats + f.mixed_repeats < p.partner_repeats + p.mixed_repeats);
+        assert_eq!(f.never_partnered.len(), 100 - 80 + f.partner_repeats);
+        assert_eq!(Season::from_text(&fitted.to_text()), Ok(fitted));
+        assert!(Season::from_text("season 2 2\nnights 1\nplayed\n1 1").is_err());
+    }
+
+    #[test]
+    fn season_nights_are_built_against_the_season() {
+        use crate::budget::Budget;
+        use crate::search::{optimize, optimize_in_season};
+        use crate::season::Season;
+        use crate::target::{by_games_per_player, by_games_per_player_in_season};
+        use crate::verify::LiveReport;
+        let obj = Objective::default();
+        let repeats = |s: &Season| s.report().partner_repeats + s.report().mixed_repeats;
+        // Part 2, as in the relabeling test: built against the season, the
+        // nights repeat fewer earlier pairings than nights built alone, with
+        // no relabeling at all.
+        let roster = Roster::new(10, 10);
+        let (mut plain, mut league) = (Season::new(roster), Season::new(roster));
+        for week in 1..=4 {
+            plain.record(&by_games_per_player(roster, 5, 2, &obj, week));
+            let night = by_games_per_player_in_season(&league, 5, 2, &obj, week);
+            let r = verify(&night, roster, 5);
+            assert!(r.is_legal(), "{:?}", r.violations);
+            assert_eq!(r.games, 10);
+            league.record(&night);
+        }
+        assert!(repeats(&league) < repeats(&plain));
+        // Replaying a recorded night repeats each of its pairings once.
+        let mut replay = LiveReport::in_season(&league, 5);
+        let last = by_games_per_player(roster, 5, 2, &obj, 9);
+        league.record(&last);
+        let mut again = LiveReport::in_season(&league, 5);
+        for round in &last.rounds {
+            replay.add_round(&round.games);
+            again.add_round(&round.games);
+        }
+        assert_eq!(again.season_repeats(), replay.season_repeats() + 4 * last.num_games());
+        assert_eq!(again.season_meetings(), replay.season_meetings() + 2 * last.num_games());
+        // Part 1 nights stay legal and maximal.
+        let roster = Roster::new(9, 5);
+        let unlimited = Budget::unlimited();
+        let mut league = Season::new(roster);
+        for week in 1..=3 {
+            let alone = optimize(roster, 2, 4_000, &obj, week);
+            let night = optimize_in_season(&league, 2, Some(4_000), &unlimited, &obj, week, &|_| {});
+            let (a, b) = (verify(&alone, roster, 2), verify(&night, roster, 2));
+            assert!(b.is_legal(), "{:?}", b.violations);
+            assert_eq!(a.games, b.games);
+            league.record(&night);
+        }
+    }
+
+    #[test]
+    fn quotas_set_the_game_count_and_are_honoured() {
+        use crate::model::Player;
+        use crate::quota::{Quota, Quotas};
+        use crate::target::by_quotas;
+        use crate::verify::verify_with;
+        // Regulars play 3–4, one man only 2, one woman 6: the men's maxima
+        // (7·4 + 2 = 30 seats) allow 15 games.
+        let roster = Roster::new(8, 8);
+        let quotas = Quotas::parse(roster, "3-4, M1:2, w1:6").unwrap();
+        assert_eq!(quotas.of(Player::M(Man(0))), Quota::exactly(2));
+        assert_eq!(quotas.total_games(), Ok(15));
+        let s = by_quotas(roster, 2, &quotas, &Objective::default(), 1).unwrap();
+        let mut rules = crate::rules::builtin();
+        rules.push(Box::new(quotas.clone()));
+        let r = verify_with(&s, roster, 2, &rules);
+        assert!(r.is_legal(), "{:?}", r.violations);
+        assert_eq!(r.games, 15);
+        assert_eq!((r.games_per_man[0], r.games_per_woman[0]), (2, 6));
+        assert_eq!(r.rule_scores, vec![("quota-miss".to_string(), 0.0)]);
+
+        // A schedule that ignores the quotas is called out player by player.
+        let even = verify_with(&greedy(roster, 2, 5, 1), roster, 2, &rules);
+        assert!(even.violations.iter().any(|v| v.to_string().starts_with("[quotas] M1 played")));
+
+        // Minima the other gender's maxima cannot seat, a min above its max,
+        // a typo and another roster's quotas are all refused.
+        assert!(Quotas::parse(roster, "5, M1:1").unwrap().total_games().is_err());
+        assert!(Quotas::parse(roster, "4-3").unwrap().total_games().is_err());
+        assert!(Quotas::parse(roster, "4, M9:2").is_err());
+        assert!(Quotas::parse(roster, "M1:2").is_err());
+        let other = Quotas::new(Roster::new(6, 6), Quota::exactly(4));
+        assert!(by_quotas(roster, 2, &other, &Objective::default(), 1).is_err());
     }
 
     #[test]
@@ -223,7 +841,7 @@ mod tests {
         use crate::target::by_games_per_player;
         // 6×6, each plays 4 → 12 games, below the 18 ceiling: no forced repeats.
         let roster = Roster::new(6, 6);
-        let s = by_games_per_player(roster, 3, 4, 1);
+        let s = by_games_per_player(roster, 3, 4, &Objective::default(), 1);
         let r = verify(&s, roster, 3);
         assert!(r.is_structurally_valid());
         assert!(r.is_legal(), "below ceiling should have no partner/opp repeats");
@@ -238,7 +856,7 @@ mod tests {
         // 6×6, each plays 8 → 24 games, past the 18 ceiling: repeats forced but
         // every ledger should land exactly on its floor.
         let roster = Roster::new(6, 6);
-        let s = by_games_per_player(roster, 3, 8, 1);
+        let s = by_games_per_player(roster, 3, 8, &Objective::default(), 1);
         let r = verify(&s, roster, 3);
         assert!(r.is_structurally_valid());
         assert_eq!(r.games, 24);
@@ -249,12 +867,206 @@ mod tests {
         assert_eq!(r.woman_repeat_excess, r.woman_repeat_floor, "woman floor");
     }
 
+    #[test]
+    fn part2_annealing_clears_repeats_greedy_leaves() {
+        use crate::target::by_games_per_player;
+        // 8×8, each plays 6 → 24 games, below the 32 ceiling. Greedy restarts
+        // alone leave a partnership repeat here; the ruin-and-recreate pass
+        // finds a legal schedule.
+        let roster = Roster::new(8, 8);
+        let s = by_games_per_player(roster, 4, 6, &Objective::default(), 1);
+        let r = verify(&s, roster, 4);
+        assert!(r.is_legal());
+        assert_eq!(r.games, 24);
+        assert_eq!(r.participation_spread(), 0);
+    }
+
+    #[test]
+    fn part2_rounds_fill_every_court() {
+        use crate::construct::construct;
+        use crate::target::by_rounds;
+        use crate::verify::Report;
+        // 9×7 on 3 courts: 5 full rounds is 15 games, 30 seats a gender.
+        let roster = Roster::new(9, 7);
+        let s = by_rounds(roster, 3, 5, &Objective::default(), 1);
+        let r = verify(&s, roster, 3);
+        assert!(r.is_legal());
+        assert_eq!((r.rounds, r.games), (5, 15));
+        assert!(s.rounds.iter().all(|round| round.games.len() == 3));
+        assert!(r.games_per_man.iter().all(|&g| g == 3 || g == 4));
+        assert!(r.games_per_woman.iter().all(|&g| g == 4 || g == 5));
+
+        // 8×8 for 8 rounds of 4 is the whole round-robin, which Part 1
+        // spreads over more rounds: the construction, annealed.
+        let roster = Roster::new(8, 8);
+        let s = by_rounds(roster, 4, 8, &Objective::default(), 1);
+        let r = verify(&s, roster, 4);
+        assert!(r.is_legal());
+        assert_eq!((r.rounds, r.games), (8, 32));
+        let fit = verify(&construct(roster).unwrap(), roster, 4);
+        let same = |r: &Report| r.man_repeat_excess + r.woman_repeat_excess;
+        assert!(same(&r) < same(&fit), "{} vs {}", same(&r), same(&fit));
+
+        // No players at all, and too few for one court: empty, no panic.
+        for roster in [Roster::new(0, 0), Roster::new(1, 1)] {
+            let s = by_rounds(roster, 2, 3, &Objective::default(), 1);
+            assert_eq!(s.num_games(), 0);
+            assert!(verify(&s, roster, 2).is_structurally_valid());
+        }
+    }
+
+    #[test]
+    fn court_plans_cap_each_round() {
+        use crate::model::CourtPlan;
+        use crate::rotation::assign_courts;
+        use crate::search::optimize;
+        use crate::target::{by_rounds, by_total_games};
+        let plan = CourtPlan::parse("2x2,4").unwrap();
+        assert_eq!(plan, CourtPlan::new(vec![2, 2, 4]));
+        assert_eq!((plan.at(1), plan.at(9), plan.slots(4)), (2, 4, 12));
+        assert_eq!(plan.to_string(), "2x2,4");
+        assert!(CourtPlan::parse("0x2,4").is_err());
+        let fits = |s: &Schedule| {
+            s.rounds.iter().enumerate().all(|(i, r)| r.games.len() <= plan.at(i) as usize)
+        };
+
+        // 8×8: the round-robin on two courts for two rounds, then four.
+        let roster = Roster::new(8, 8);
+        let s = optimize(roster, &plan, 20_000, &Objective::courts(), 1);
+        let s = assign_courts(&s, roster, &plan);
+        let r = verify(&s, roster, &plan);
+        assert!(r.is_legal() && fits(&s));
+        assert_eq!((r.games, r.rounds, r.round_floor), (32, 9, 9));
+        assert_eq!(r.court_utilization, 1.0);
+        // The later rounds do use courts 3 and 4.
+        let on_two = verify(&s, roster, 2).violations;
+        assert!(on_two.iter().any(|v| matches!(v, Violation::CourtOutOfRange { .. })));
+
+        // Part 2 fills each round to what it offers: 2 + 2 + 4·3 = 16 games.
+        let roster = Roster::new(10, 10);
+        let s = by_rounds(roster, &plan, 5, &Objective::default(), 1);
+        let r = verify(&s, roster, &plan);
+        assert!(r.is_legal() && fits(&s));
+        assert_eq!((r.rounds, r.games, r.court_utilization), (5, 16, 1.0));
+        let s = by_total_games(roster, &plan, 30, &Objective::default(), 1);
+        assert!(verify(&s, roster, &plan).is_legal() && fits(&s));
+    }
+
+    #[test]
+    fn side_games_use_byes_outside_the_ledgers() {
+        use crate::model::SideGame;
+        use crate::side::{fill_byes, SideOptions};
+        use crate::target::by_total_games;
+        use crate::verify::Slot;
+        // 7×5 on 4 courts, two games a round: 3 men and a woman sit out, and
+        // two spare courts take them all as singles.
+        let roster = Roster::new(7, 5);
+        let s = by_total_games(roster, 4, 8, &Objective::default(), 1);
+        let before = verify(&s, roster, 4);
+        let filled = fill_byes(&s, roster, 4, &SideOptions::default());
+        let r = verify(&filled, roster, 4);
+        assert!(r.is_legal());
+        assert_eq!((r.games, r.man_repeat_excess), (8, before.man_repeat_excess));
+        assert_eq!(r.byes_per_round, before.byes_per_round);
+        assert_eq!((r.side_games, r.drill_slots, r.side_repeat_excess), (8, 0, 0));
+        assert!(r.side_games_per_man.iter().all(|&g| g == 1 || g == 2));
+
+        // Without singles the leftovers drill; nobody is double-booked.
+        let only = SideOptions { singles: false, ..SideOptions::default() };
+        let r = verify(&fill_byes(&s, roster, 4, &only), roster, 4);
+        assert!(r.is_legal());
+        assert_eq!((r.side_games, r.drill_slots), (0, 16));
+
+        // A side game must take players on a bye, on a court of its own.
+        let mut bad = filled.clone();
+        let playing = Player::M(bad.rounds[0].games[0].a.man);
+        let court = bad.rounds[0].court(1);
+        bad.rounds[0].side = vec![SideGame::Singles { court, a: playing, b: Player::W(Woman(0)) }];
+        let r = verify(&bad, roster, 4);
+        let booked = r
+            .violations
+            .iter()
+            .find(|v| matches!(v, Violation::SideBooked { who, .. } if *who == playing));
+        // It names the game the player is already in and the side game's court.
+        let mut want = [Slot::of(&bad.rounds[0], 0, 0), Slot { round: 0, court: court as usize }];
+        want.sort();
+        assert_eq!(booked.map(Violation::slots), Some(&want[..]));
+        assert!(r.violations.iter().any(|v| matches!(v, Violation::CourtClash { .. })));
+    }
+
+    #[test]
+    fn officials_come_from_byes_and_skip_next_opponents() {
+        use crate::duty::{assign_officials, duty_counts};
+        use crate::target::by_games_per_player;
+        // 11×9 on 4 courts: four sit out a full round, enough for every game.
+        let roster = Roster::new(11, 9);
+        let s = by_games_per_player(roster, 4, 6, &Objective::default(), 1);
+        let o = assign_officials(&s, roster);
+        assert!(verify(&o, roster, 4).is_legal());
+        for (ri, round) in o.rounds.iter().enumerate() {
+            assert_eq!(round.officials.len(), round.games.len());
+            for (gi, g) in round.games.iter().enumerate() {
+                let p = round.official(gi).expect("every game has a bye to spare");
+                assert!(!round.active_players().contains(&p));
+                // Nobody scores a game their next opponents play in.
+                let Some(next) = o.rounds.get(ri + 1) else { continue };
+                if let Some(h) = next.games.iter().find(|h| h.players().contains(&p)) {
+                    let mine = [Player::M(h.a.man), Player::W(h.a.woman)].contains(&p);
+                    let facing = if mine { h.b } else { h.a };
+                    assert!(!g.players().contains(&Player::M(facing.man)));
+                    assert!(!g.players().contains(&Player::W(facing.woman)));
+                }
+            }
+        }
+        let duties = duty_counts(&o, roster);
+        assert_eq!(duties.iter().sum::<usize>(), verify(&s, roster, 4).games);
+        assert!(duties.iter().max().unwrap() - duties.iter().min().unwrap() <= 2, "{:?}", duties);
+
+        // Rebuilding a round drops its officials.
+        assert!(crate::rotation::assign_courts(&o, roster, 4).rounds[0].officials.is_empty());
+    }
+
+    #[test]
+    fn mixer_meets_the_most_people() {
+        use crate::target::by_games_per_player;
+        // 16×16, five games each on 4 courts: 15 new faces each at best.
+        let roster = Roster::new(16, 16);
+        let mixer = verify(&by_games_per_player(roster, 4, 5, &Objective::mixer(), 1), roster, 4);
+        assert!(mixer.is_legal());
+        assert!(mixer.games_per_man.iter().chain(&mixer.games_per_woman).all(|&g| g == 5));
+        assert!(mixer.met_per_man.iter().chain(&mixer.met_per_woman).all(|&m| m == 15));
+        assert_eq!(mixer.met_spread(), 0);
+
+        let plain = verify(&by_games_per_player(roster, 4, 5, &Objective::default(), 1), roster, 4);
+        let total =
+            |r: &crate::verify::Report| r.met_per_man.iter().chain(&r.met_per_woman).sum::<usize>();
+        assert!(total(&plain) < total(&mixer));
+        let weighted = Objective::default().with_overrides("coverage:100").unwrap();
+        assert_eq!(weighted, Objective::mixer());
+    }
+
+    #[test]
+    fn part2_per_gender_targets_are_exact() {
+        use crate::target::{by_games_per_gender, games_for_per_gender};
+        // 12×8: men 4, women 6 → 48 seats a gender, 24 games.
+        let roster = Roster::new(12, 8);
+        let s = by_games_per_gender(roster, 4, 4, 6, &Objective::default(), 1).unwrap();
+        let r = verify(&s, roster, 4);
+        assert!(r.is_legal());
+        assert_eq!(r.games, 24);
+        assert!(r.games_per_man.iter().all(|&g| g == 4));
+        assert!(r.games_per_woman.iter().all(|&g| g == 6));
+        // 48 men's seats against 40 women's; 3×3 seats cannot pair up.
+        assert!(games_for_per_gender(roster, 4, 5).is_err());
+        assert!(games_for_per_gender(Roster::new(3, 3), 3, 3).is_err());
+    }
+
     #[test]
     fn part2_total_cap_is_exact() {
         use crate::target::by_total_games;
         let roster = Roster::new(6, 6);
         for g in [7usize, 9, 10, 15] {
-            let s = by_total_games(roster, 3, g, 2);
+            let s = by_total_games(roster, 3, g, &Objective::default(), 2);
             let r = verify(&s, roster, 3);
             assert!(r.is_structurally_valid());
             assert_eq!(r.games, g, "total cap not exact for g={}", g);
@@ -266,7 +1078,7 @@ mod tests {
         use crate::target::by_games_per_player;
         // each = n at full courts is the full round-robin → Part 1 optimum.
         let roster = Roster::new(10, 10);
-        let s = by_games_per_player(roster, 5, 10, 1);
+        let s = by_games_per_player(roster, 5, 10, &Objective::default(), 1);
         let r = verify(&s, roster, 5);
         assert!(r.is_legal());
         assert_eq!(r.games, 50);
@@ -277,9 +1089,9 @@ mod tests {
     #[test]
     fn courts_emphasis_fills_courts_on_6x6() {
         // Courts emphasis should pack 18 games into 6 full rounds (0 byes).
-        use crate::search::{optimize, EMPHASIS_COURTS};
+        use crate::search::optimize;
         let roster = Roster::new(6, 6);
-        let s = optimize(roster, 3, 60_000, EMPHASIS_COURTS, 3);
+        let s = optimize(roster, 3, 60_000, &Objective::courts(), 3);
         let r = verify(&s, roster, 3);
         assert!(r.is_legal());
         assert_eq!(r.games, 18);
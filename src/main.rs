@@ -1,69 +1,498 @@
 //! CLI entry point.
 //!
 //! Part 1 (maximize games): `pickleball_scheduler [men] [women] [courts] [emphasis] [ls_iters] [seed]`
-//!   (defaults: 6 6 3 balanced 40000) — `emphasis` is `courts` | `balanced` | `variety`.
+//!   (defaults: 6 6 3 balanced 40000) — `emphasis` is `courts` | `balanced` | `variety`,
+//!   or `frontier` to list every courts ⇄ variety trade-off and print the one
+//!   chosen with `pick=K` (default 1).
 //!
-//! Part 2 (target a fixed amount of play): add a token `each=N` or `total=G`,
-//!   e.g. `pickleball_scheduler 8 8 4 each=6` or `... total=30`.
+//! Courts: `courts` may also change round by round, as `COURTSxROUNDS,…` with
+//!   the last count continuing, e.g. `pickleball_scheduler 10 10 2x3,4 each=6`
+//!   has two courts for the first three rounds and four after.
+//!
+//! Exact: emphasis `exact` searches small rosters (at most 64 partnerships)
+//!   exhaustively and says whether the result is proven optimal, e.g.
+//!   `pickleball_scheduler 5 5 2 exact` (add `time=SECS` to cap the proof).
+//!
+//! Part 2 (target a fixed amount of play): add a token `each=N`, `total=G` or
+//!   `rounds=R` (every court full for exactly `R` rounds),
+//!   e.g. `pickleball_scheduler 8 8 4 each=6`, `... total=30` or `... rounds=5`.
+//!   For `M ≠ W`, `each=A/B` has every man play exactly `A` games and every
+//!   woman `B`, e.g. `pickleball_scheduler 8 6 3 each=6/8`.
+//!   Emphasis `mixer` has everyone meet as many different people as the games
+//!   allow, e.g. `pickleball_scheduler 12 12 3 mixer rounds=4`.
+//!
+//! Quotas: add `quotas=DEFAULT,PLAYER:N,…` to give players their own game
+//!   counts (`N` or `MIN-MAX`), e.g. `quotas=4-6,M1:3,W2:6`; the report then
+//!   lists everyone outside their quota.
+//!
+//! Session: add `session=MIN` or `session=HH:MM-HH:MM` to fill as many full
+//!   rounds as the booked time holds, with `game=MIN` (default 12),
+//!   `changeover=MIN` and `break=AT+MIN` (`AT` a time of day or minutes in),
+//!   e.g. `pickleball_scheduler 10 10 4 session=19:00-21:00 game=12 changeover=2`.
+//!
+//! Season: add `season=FILE` to keep a league's history between nights. The
+//!   night is built to repeat as few of the season's pairings as it can,
+//!   relabeled so its games land on the pairs that have met least (and its
+//!   extra games on whoever has played least) over the season, then
+//!   recorded in `FILE` (created on the first night) and summarized, e.g.
+//!   `pickleball_scheduler 10 10 5 each=4 season=league.txt`.
+//!
+//! Side games: add `side=all` (or any of `doubles,singles,drill`) to put byes
+//!   to use — same-gender doubles or singles on spare courts, a drill for the
+//!   rest — rotated across the schedule and kept out of its ledgers, e.g.
+//!   `pickleball_scheduler 11 9 4 each=6 side=all`.
+//!
+//! Officials: add `officials=on` to have players on a bye officiate games,
+//!   duties shared evenly and nobody officiating their next-round opponent,
+//!   e.g. `pickleball_scheduler 11 9 4 each=6 officials=on`.
+//!
+//! Compare: add `vs-seed=S` and/or `vs-emphasis=E` to also build the schedule
+//!   with that seed/emphasis and print what changed, e.g.
+//!   `pickleball_scheduler 8 8 4 balanced 40000 1 vs-seed=2`.
+//!
+//! Time budget: add `time=SECS` to run Part 1 for that long instead of a fixed
+//!   number of steps, e.g. `pickleball_scheduler 12 12 6 balanced time=2`.
+//!
+//! Weights: add `weights=name:value,…` to tune the objective on top of the
+//!   emphasis preset, e.g. `weights=excess:20,spread:50` (names: max-meetings,
+//!   excess, spread, rounds for Part 1; repeat, fairness, same-gender,
+//!   coverage for Part 2; history for a season's nights).
+//!
+//! Threads: restarts use every core; `threads=N` caps that (results are the
+//!   same for any `N`).
 
-use pickleball_scheduler::model::Roster;
-use pickleball_scheduler::report::{print_report, print_schedule};
-use pickleball_scheduler::search::{optimize, EMPHASIS_BALANCED, EMPHASIS_COURTS, EMPHASIS_VARIETY};
-use pickleball_scheduler::target::{by_games_per_player, by_total_games};
-use pickleball_scheduler::verify::verify;
+use pickleball_scheduler::budget::Budget;
+use pickleball_scheduler::diff::diff;
+use pickleball_scheduler::duty::assign_officials;
+use pickleball_scheduler::exact::solve;
+use pickleball_scheduler::model::{CourtPlan, Roster, Schedule};
+use pickleball_scheduler::objective::Objective;
+use pickleball_scheduler::pack::pack_exact;
+use pickleball_scheduler::parallel::set_threads;
+use pickleball_scheduler::quota::Quotas;
+use pickleball_scheduler::report::{
+    print_diff, print_frontier, print_report, print_schedule, print_season, print_timetable,
+};
+use pickleball_scheduler::rotation::assign_courts;
+use pickleball_scheduler::rules::{self, Rule};
+use pickleball_scheduler::search::{
+    optimize_frontier, optimize_in_season, optimize_with_progress, Progress,
+};
+use pickleball_scheduler::season::Season;
+use pickleball_scheduler::session::{parse_clock, Session};
+use pickleball_scheduler::side::{fill_byes, SideOptions};
+use pickleball_scheduler::target::{
+    by_games_per_gender, by_games_per_gender_in_season, by_games_per_player,
+    by_games_per_player_in_season, by_quotas, by_rounds, by_rounds_in_season, by_session,
+    by_session_in_season, by_total_games, by_total_games_in_season, games_for_per_gender,
+};
+use pickleball_scheduler::verify::{verify_with, Report};
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
 fn arg<T: std::str::FromStr>(args: &[String], i: usize, default: T) -> T {
     args.get(i).and_then(|s| s.parse().ok()).unwrap_or(default)
 }
 
-/// Scan for a Part 2 target token `each=N` / `total=G`.
+/// The positional court count or per-round plan (default 3). A bad plan is
+/// fatal, like a bad weight.
+fn courts(args: &[String]) -> CourtPlan {
+    let Some(spec) = args.get(3) else {
+        return CourtPlan::uniform(3);
+    };
+    CourtPlan::parse(spec).unwrap_or_else(|err| {
+        eprintln!("error: {}", err);
+        std::process::exit(2);
+    })
+}
+
+/// The value of the first `key=value` token, if any.
+fn token<'a>(args: &'a [String], key: &str) -> Option<&'a str> {
+    args.iter()
+        .find_map(|a| a.strip_prefix(key).and_then(|v| v.strip_prefix('=')))
+}
+
+/// Scan for a Part 2 target token `each=N` / `total=G` / `rounds=R`.
 fn part2_target(args: &[String]) -> Option<(&'static str, u32)> {
     for a in args {
-        if let Some(v) = a.strip_prefix("each=") {
-            if let Ok(n) = v.parse() {
-                return Some(("each", n));
+        for key in ["each", "total", "rounds"] {
+            let value = a.strip_prefix(key).and_then(|v| v.strip_prefix('='));
+            if let Some(n) = value.and_then(|v| v.parse().ok()) {
+                return Some((key, n));
+            }
+        }
+    }
+    None
+}
+
+/// The per-gender `each=A/B` target and its game count, if given. Targets
+/// that cannot both be met are fatal, like a bad weight.
+fn gender_target(args: &[String], roster: Roster) -> Option<(u32, u32, usize)> {
+    let (a, b) = token(args, "each")?.split_once('/')?;
+    let parsed = a.trim().parse().ok().zip(b.trim().parse().ok());
+    let ((a, b), games) = parsed
+        .ok_or_else(|| format!("`each={}/{}` is not each=MEN/WOMEN", a, b))
+        .and_then(|(a, b)| games_for_per_gender(roster, a, b).map(|g| ((a, b), g)))
+        .unwrap_or_else(|err| {
+            eprintln!("error: {}", err);
+            std::process::exit(2);
+        });
+    Some((a, b, games))
+}
+
+/// The `quotas=…` target, if given. A bad spec is fatal, like a bad weight.
+fn quotas(args: &[String], roster: Roster) -> Option<Quotas> {
+    let spec = token(args, "quotas")?;
+    Some(Quotas::parse(roster, spec).and_then(|q| q.total_games().map(|_| q)).unwrap_or_else(|err| {
+        eprintln!("error: {}", err);
+        std::process::exit(2);
+    }))
+}
+
+/// The `session=…` target and its timing tokens, if given. A bad token, or a
+/// session too short for one game, is fatal like a bad weight.
+fn session(args: &[String]) -> Option<Session> {
+    let spec = token(args, "session")?;
+    Some(parse_session(args, spec).unwrap_or_else(|err| {
+        eprintln!("error: {}", err);
+        std::process::exit(2);
+    }))
+}
+
+fn parse_session(args: &[String], spec: &str) -> Result<Session, String> {
+    const DAY: u32 = 24 * 60;
+    let minutes = |key: &str, v: &str| {
+        v.parse::<u32>().map_err(|_| format!("{key} `{v}` is not a number of minutes"))
+    };
+    let (start, length) = match spec.split_once('-') {
+        Some((from, to)) => {
+            let (from, to) = (parse_clock(from)?, parse_clock(to)?);
+            (from, (to + DAY - from) % DAY)
+        }
+        None => (0, minutes("session", spec)?),
+    };
+    let game = token(args, "game").map_or(Ok(12), |v| minutes("game", v))?;
+    if game == 0 {
+        return Err("game `0`: a game must take at least one minute".to_string());
+    }
+    let changeover = token(args, "changeover").map_or(Ok(0), |v| minutes("changeover", v))?;
+    let mut session = Session::new(length, game).starting_at(start).changeover(changeover);
+    if let Some(spec) = token(args, "break") {
+        let (at, len) = spec
+            .split_once('+')
+            .ok_or_else(|| format!("break `{spec}` is not AT+MINUTES"))?;
+        let at = if at.contains(':') {
+            (parse_clock(at)? + DAY - start) % DAY
+        } else {
+            minutes("break", at)?
+        };
+        session = session.with_break(at, minutes("break", len)?);
+    }
+    if session.rounds() == 0 {
+        return Err(format!("session `{spec}` is too short for one {game}-minute game"));
+    }
+    Ok(session)
+}
+
+/// The emphasis preset with any `weights=name:value,…` overrides applied.
+/// A bad spec is fatal: silently ignoring a weight would mislead.
+fn objective(args: &[String], emphasis: &str) -> Objective {
+    let preset = match emphasis {
+        "courts" => Objective::courts(),
+        "variety" => Objective::variety(),
+        "mixer" => Objective::mixer(),
+        _ => Objective::balanced(),
+    };
+    preset.with_overrides(token(args, "weights").unwrap_or("")).unwrap_or_else(|err| {
+        eprintln!("error: {}", err);
+        std::process::exit(2);
+    })
+}
+
+/// Part 1's search budget: `time=SECS` if given, else the `ls_iters` step
+/// count (argument 5).
+fn part1_budget(args: &[String]) -> (Option<u32>, Budget) {
+    match token(args, "time").and_then(|v| v.parse::<f64>().ok()) {
+        Some(secs) => (None, Budget::within(Duration::from_secs_f64(secs.max(0.0)))),
+        None => (Some(arg(args, 5, 40_000)), Budget::unlimited()),
+    }
+}
+
+/// A live progress line on stderr, redrawn at most ten times a second, and
+/// only when stderr is a terminal (so piped output stays clean).
+fn progress_line() -> impl Fn(&Progress) + Sync {
+    let tty = std::io::stderr().is_terminal();
+    let last = Mutex::new(None::<Instant>);
+    move |p: &Progress| {
+        if !tty {
+            return;
+        }
+        let mut last = last.lock().unwrap();
+        if last.is_some_and(|t| t.elapsed() < Duration::from_millis(100)) {
+            return;
+        }
+        *last = Some(Instant::now());
+        let steps = p.steps.map_or("?".to_string(), |n| n.to_string());
+        eprint!(
+            "\r\x1b[K  start {}/{}  step {}/{}  games {}  rounds {}  same-gender +{}/+{}  T {:.2}",
+            p.start + 1,
+            p.starts,
+            p.step,
+            steps,
+            p.games,
+            p.rounds,
+            p.man_excess,
+            p.woman_excess,
+            p.temperature
+        );
+    }
+}
+
+/// The `season=FILE` history and its path, if given: loaded if the file
+/// exists, a new season otherwise. An unreadable file, one for another
+/// roster, or a season combined with per-player quotas is fatal.
+fn season(args: &[String], roster: Roster) -> Option<(PathBuf, Season)> {
+    let path = PathBuf::from(token(args, "season")?);
+    Some(load_season(args, roster, &path).map(|s| (path, s)).unwrap_or_else(|err| {
+        eprintln!("error: {}", err);
+        std::process::exit(2);
+    }))
+}
+
+fn load_season(args: &[String], roster: Roster, path: &Path) -> Result<Season, String> {
+    if token(args, "quotas").is_some() {
+        return Err("season= relabels players, so it can't be combined with quotas=".to_string());
+    }
+    if !path.exists() {
+        return Ok(Season::new(roster));
+    }
+    let season = Season::load(path)?;
+    if season.roster() != roster {
+        let other = season.roster();
+        let (m, w) = (other.men, other.women);
+        return Err(format!("{} is a season for {} men × {} women", path.display(), m, w));
+    }
+    Ok(season)
+}
+
+/// The `side=…` activities for byes, if given. A bad spec is fatal, like a
+/// bad weight.
+fn side_options(args: &[String]) -> Option<SideOptions> {
+    let spec = token(args, "side")?;
+    Some(SideOptions::parse(spec).unwrap_or_else(|err| {
+        eprintln!("error: {}", err);
+        std::process::exit(2);
+    }))
+}
+
+/// Whether `officials=on` asks for officials. Anything but `on` or `off` is
+/// fatal, like a bad side spec.
+fn officials(args: &[String]) -> bool {
+    match token(args, "officials") {
+        None | Some("off") => false,
+        Some("on") => true,
+        Some(other) => {
+            eprintln!("error: officials={} is not on or off", other);
+            std::process::exit(2);
+        }
+    }
+}
+
+/// Build the schedule the arguments ask for, with `emphasis` and `seed` given
+/// explicitly so a comparison run can override them. For a `season` the
+/// night is built against its ledgers. Courts are rotated last, then players
+/// are relabeled for `season` if given, then byes get side games and
+/// officiating duties if asked for.
+fn build(
+    args: &[String],
+    roster: Roster,
+    courts: &CourtPlan,
+    emphasis: &str,
+    seed: u64,
+    season: Option<&Season>,
+) -> Schedule {
+    let obj = objective(args, emphasis);
+    let schedule = match (session(args), part2_target(args)) {
+        _ if token(args, "quotas").is_some() => {
+            let quotas = quotas(args, roster).expect("quotas token present");
+            by_quotas(roster, courts, &quotas, &obj, seed).expect("quotas checked when parsed")
+        }
+        _ if gender_target(args, roster).is_some() => {
+            let (a, b, _) = gender_target(args, roster).expect("each=A/B token present");
+            match season {
+                Some(season) => by_games_per_gender_in_season(season, courts, a, b, &obj, seed),
+                None => by_games_per_gender(roster, courts, a, b, &obj, seed),
             }
+            .expect("checked when parsed")
         }
-        if let Some(v) = a.strip_prefix("total=") {
-            if let Ok(n) = v.parse() {
-                return Some(("total", n));
+        (Some(s), _) => match season {
+            Some(season) => by_session_in_season(season, courts, &s, &obj, seed),
+            None => by_session(roster, courts, &s, &obj, seed),
+        },
+        (_, Some(("each", n))) => match season {
+            Some(season) => by_games_per_player_in_season(season, courts, n, &obj, seed),
+            None => by_games_per_player(roster, courts, n, &obj, seed),
+        },
+        (_, Some(("total", g))) => match season {
+            Some(season) => by_total_games_in_season(season, courts, g as usize, &obj, seed),
+            None => by_total_games(roster, courts, g as usize, &obj, seed),
+        },
+        (_, Some(("rounds", r))) => match season {
+            Some(season) => by_rounds_in_season(season, courts, r as usize, &obj, seed),
+            None => by_rounds(roster, courts, r as usize, &obj, seed),
+        },
+        _ => {
+            let (ls_iters, budget) = part1_budget(args);
+            let show = progress_line();
+            let s = match season {
+                Some(season) => {
+                    optimize_in_season(season, courts, ls_iters, &budget, &obj, seed, &show)
+                }
+                None => optimize_with_progress(roster, courts, ls_iters, &budget, &obj, seed, &show),
+            };
+            if std::io::stderr().is_terminal() {
+                eprint!("\r\x1b[K");
             }
+            s
         }
+    };
+    let schedule = assign_courts(&schedule, roster, courts);
+    let schedule = match season {
+        Some(season) => season.fit(&schedule, &obj, seed),
+        None => schedule,
+    };
+    let schedule = match side_options(args) {
+        Some(options) => fill_byes(&schedule, roster, courts, &options),
+        None => schedule,
+    };
+    if officials(args) {
+        assign_officials(&schedule, roster)
+    } else {
+        schedule
     }
-    None
 }
 
 fn main() {
     let args: Vec<String> = std::env::args().collect();
     let men: u16 = arg(&args, 1, 6);
     let women: u16 = arg(&args, 2, 6);
-    let courts: u16 = arg(&args, 3, 3);
+    let courts = courts(&args);
     let seed: u64 = arg(&args, 6, 0xDEAD_BEEF);
     let roster = Roster::new(men, women);
+    let emphasis = args.get(4).map(String::as_str).unwrap_or("balanced");
+    if let Some(n) = token(&args, "threads").and_then(|v| v.parse().ok()) {
+        set_threads(n);
+    }
 
-    let schedule = match part2_target(&args) {
-        Some(("each", n)) => {
-            println!("(Part 2: each player plays ~{} games)", n);
-            by_games_per_player(roster, courts, n, seed)
+    let timing = session(&args);
+    let quotas = quotas(&args, roster);
+    match part2_target(&args) {
+        _ if quotas.is_some() => {
+            let games = quotas.as_ref().map_or(0, |q| q.total_games().unwrap_or(0));
+            println!("(Part 2: per-player quotas — {} games)", games);
         }
-        Some(("total", g)) => {
-            println!("(Part 2: cap at {} total games)", g);
-            by_total_games(roster, courts, g as usize, seed)
+        _ if gender_target(&args, roster).is_some() => {
+            let (a, b, games) = gender_target(&args, roster).expect("each=A/B token present");
+            println!("(Part 2: men play {} games, women {} — {} games)", a, b, games);
         }
-        _ => {
-            let emphasis = args.get(4).map(String::as_str).unwrap_or("balanced");
-            let ls_iters: u32 = arg(&args, 5, 40_000);
-            let round_weight = match emphasis {
-                "courts" => EMPHASIS_COURTS,
-                "variety" => EMPHASIS_VARIETY,
-                _ => EMPHASIS_BALANCED,
-            };
-            println!("(Part 1: maximize games — emphasis: {})", emphasis);
-            optimize(roster, courts, ls_iters, round_weight, seed)
+        _ if timing.is_some() => {
+            let rounds = timing.map_or(0, |s| s.rounds());
+            println!("(Part 2: the {} rounds that fit the session)", rounds);
         }
-    };
+        Some(("each", n)) => println!("(Part 2: each player plays ~{} games)", n),
+        Some(("total", g)) => println!("(Part 2: cap at {} total games)", g),
+        Some(("rounds", r)) => println!("(Part 2: exactly {} full rounds)", r),
+        _ if emphasis == "frontier" => return show_frontier(&args, roster, &courts, seed),
+        _ if emphasis == "exact" => return show_exact(&args, roster, &courts),
+        _ => println!("(Part 1: maximize games — emphasis: {})", emphasis),
+    }
+    let mut season = season(&args, roster);
+    let schedule = build(&args, roster, &courts, emphasis, seed, season.as_ref().map(|(_, s)| s));
+
+    print_schedule(&schedule, roster, courts.most());
+    print_report(&report(&schedule, roster, &courts, quotas.as_ref()));
+    if let Some(s) = &timing {
+        print_timetable(s);
+    }
+
+    let vs_seed = token(&args, "vs-seed").and_then(|v| v.parse().ok());
+    let vs_emphasis = token(&args, "vs-emphasis");
+    if vs_seed.is_some() || vs_emphasis.is_some() {
+        let (seed2, emphasis2) = (vs_seed.unwrap_or(seed), vs_emphasis.unwrap_or(emphasis));
+        println!();
+        println!("(Compared against: seed {} — emphasis: {})", seed2, emphasis2);
+        let other = build(&args, roster, &courts, emphasis2, seed2, season.as_ref().map(|(_, s)| s));
+        print_schedule(&other, roster, courts.most());
+        print_diff(&diff(&schedule, &other, roster, &courts));
+    }
+    if let Some((path, s)) = &mut season {
+        s.record(&schedule);
+        if let Err(err) = s.save(path) {
+            eprintln!("error: {}", err);
+            std::process::exit(2);
+        }
+        print_season(&s.report());
+    }
+}
+
+/// Part 1 as a menu: list the non-dominated trade-offs, then print the one
+/// `pick=K` selects.
+fn show_frontier(args: &[String], roster: Roster, courts: &CourtPlan, seed: u64) {
+    println!("(Part 1: maximize games — every courts ⇄ variety trade-off)");
+    let (ls_iters, budget) = part1_budget(args);
+    let obj = objective(args, "frontier");
+    let options = optimize_frontier(roster, courts, ls_iters, &budget, &obj, seed);
+    print_frontier(&options);
+    let pick = token(args, "pick")
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(1usize)
+        .clamp(1, options.len());
+    println!("(Showing option {} of {} — add pick=K for another)", pick, options.len());
+    let schedule = assign_courts(&options[pick - 1].0, roster, courts);
+    print_schedule(&schedule, roster, courts.most());
+    print_report(&report(&schedule, roster, courts, None));
+}
 
+/// Part 1 solved exactly: the best schedule and whether it is proven so.
+fn show_exact(args: &[String], roster: Roster, courts: &CourtPlan) {
+    println!("(Part 1: maximize games — exact search)");
+    if !courts.is_uniform() {
+        eprintln!("error: the exact search needs the same courts every round, not `{}`", courts);
+        std::process::exit(2);
+    }
+    let courts = courts.most();
+    let budget = match token(args, "time").and_then(|v| v.parse::<f64>().ok()) {
+        Some(secs) => Budget::within(Duration::from_secs_f64(secs.max(0.0))),
+        None => Budget::unlimited(),
+    };
+    let (schedule, report) = solve(roster, courts, &budget).unwrap_or_else(|err| {
+        eprintln!("error: {}", err);
+        std::process::exit(2);
+    });
     print_schedule(&schedule, roster, courts);
-    let report = verify(&schedule, roster, courts);
     print_report(&report);
+    if !report.optimal {
+        println!("(Not proven optimal: the search ran out of time or nodes first.)");
+    }
+}
+
+/// The verifier's report, with the round floor raised to whatever an exact
+/// repack of these games can prove on a uniform plan (the counting floor can
+/// be loose), and each player's games checked against `quotas` if given. The
+/// repack stops on its node cap, not the clock, so the floor is the same on
+/// every machine.
+fn report(schedule: &Schedule, roster: Roster, courts: &CourtPlan, quotas: Option<&Quotas>) -> Report {
+    let mut rules = rules::builtin();
+    if let Some(q) = quotas {
+        rules.push(Box::new(q.clone()) as Box<dyn Rule>);
+    }
+    let mut report = verify_with(schedule, roster, courts, &rules);
+    if courts.is_uniform() {
+        let proven = pack_exact(schedule, roster, courts.most(), &Budget::unlimited()).bound.rounds();
+        report.round_floor = report.round_floor.max(proven);
+    }
+    report.optimal = report.meets_bounds();
+    report
 }
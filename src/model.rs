@@ -44,7 +44,7 @@ impl fmt::Display for Woman {
 }
 
 /// A mixed-doubles team: one man partnered with one woman.
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
 pub struct Team {
     pub man: Man,
     pub woman: Woman,
@@ -63,7 +63,7 @@ impl fmt::Display for Team {
 }
 
 /// A single game: team `a` versus team `b`.
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
 pub struct Game {
     pub a: Team,
     pub b: Team,
@@ -118,6 +118,12 @@ impl Game {
         ]
     }
 
+    /// The same game with its teams in canonical order (smaller team first), so
+    /// `a vs b` and `b vs a` compare equal.
+    pub fn canonical(&self) -> Game {
+        if self.a <= self.b { *self } else { Game::new(self.b, self.a) }
+    }
+
     /// Structurally well-formed: two distinct men and two distinct women.
     pub fn is_well_formed(&self) -> bool {
         self.a.man != self.b.man && self.a.woman != self.b.woman
@@ -146,26 +152,114 @@ impl fmt::Display for Player {
     }
 }
 
+/// Something for players on a bye to do: a side game on a spare court or a
+/// drill off it. Side games stand outside every ledger of the mixed-doubles
+/// schedule — see [`side`](crate::side).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SideGame {
+    /// Same-gender doubles: two men against two men, or women against women.
+    Doubles { court: u16, a: [Player; 2], b: [Player; 2] },
+    /// Singles, one player against another.
+    Singles { court: u16, a: Player, b: Player },
+    /// A drill for whoever is left, needing no court.
+    Drill { players: Vec<Player> },
+}
+
+impl SideGame {
+    /// Everyone taking part.
+    pub fn players(&self) -> Vec<Player> {
+        match self {
+            SideGame::Doubles { a, b, .. } => vec![a[0], a[1], b[0], b[1]],
+            SideGame::Singles { a, b, .. } => vec![*a, *b],
+            SideGame::Drill { players } => players.clone(),
+        }
+    }
+
+    /// The court it is played on; `None` for a drill.
+    pub fn court(&self) -> Option<u16> {
+        match *self {
+            SideGame::Doubles { court, .. } | SideGame::Singles { court, .. } => Some(court),
+            SideGame::Drill { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for SideGame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SideGame::Doubles { a, b, .. } => write!(f, "{} & {} vs {} & {}", a[0], a[1], b[0], b[1]),
+            SideGame::Singles { a, b, .. } => write!(f, "{} vs {}", a, b),
+            SideGame::Drill { players } => {
+                let names: Vec<String> = players.iter().map(Player::to_string).collect();
+                write!(f, "drill: {}", names.join(", "))
+            }
+        }
+    }
+}
+
 /// A round: a set of games played simultaneously, one per court. No player may
 /// appear twice in a round (nobody is on two courts at once).
-#[derive(Clone, Debug, Default)]
+///
+/// Courts are first-class: `courts[i]` is the (0-based) court game `i` is
+/// played on. [`Round::new`] puts game `i` on court `i`; the court-rotation
+/// pass ([`rotation`](crate::rotation)) reassigns them so players see every
+/// court about equally often.
+///
+/// `side` holds the round's [`SideGame`]s for players on a bye, and
+/// `officials[i]` who officiates game `i`; the builders leave both empty, for
+/// [`side::fill_byes`](crate::side::fill_byes) and
+/// [`duty::assign_officials`](crate::duty::assign_officials) to fill.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct Round {
     pub games: Vec<Game>,
+    pub courts: Vec<u16>,
+    pub side: Vec<SideGame>,
+    pub officials: Vec<Option<Player>>,
 }
 
 impl Round {
     pub fn new(games: Vec<Game>) -> Self {
-        Round { games }
+        let courts = (0..games.len() as u16).collect();
+        Round { games, courts, side: Vec::new(), officials: Vec::new() }
+    }
+
+    /// Games with explicit courts, parallel to `games`.
+    pub fn with_courts(games: Vec<Game>, courts: Vec<u16>) -> Self {
+        assert_eq!(games.len(), courts.len(), "one court per game");
+        Round { games, courts, side: Vec::new(), officials: Vec::new() }
+    }
+
+    /// Who officiates game `i`, if anyone does.
+    pub fn official(&self, i: usize) -> Option<Player> {
+        self.officials.get(i).copied().flatten()
+    }
+
+    /// The court game `i` is played on (its index, if none was assigned).
+    pub fn court(&self, i: usize) -> u16 {
+        self.courts.get(i).copied().unwrap_or(i as u16)
+    }
+
+    /// The game on court `c`, if that court is in use this round.
+    pub fn on_court(&self, c: u16) -> Option<&Game> {
+        (0..self.games.len())
+            .find(|&i| self.court(i) == c)
+            .map(|i| &self.games[i])
     }
 
-    /// Players active this round (those *not* on a bye).
+    /// Players active this round (those *not* on a bye). Side games don't
+    /// count: their players are still on a bye from the schedule proper.
     pub fn active_players(&self) -> Vec<Player> {
         self.games.iter().flat_map(|g| g.players()).collect()
     }
+
+    /// The side game on court `c`, if a spare court is in use for one.
+    pub fn side_on_court(&self, c: u16) -> Option<&SideGame> {
+        self.side.iter().find(|s| s.court() == Some(c))
+    }
 }
 
 /// A complete schedule: an ordered list of rounds.
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct Schedule {
     pub rounds: Vec<Round>,
 }
@@ -205,6 +299,15 @@ impl Roster {
         self.men as usize + self.women as usize
     }
 
+    /// `p`'s index among all [`total_players`](Roster::total_players): the
+    /// men first, then the women.
+    pub fn seat(&self, p: Player) -> usize {
+        match p {
+            Player::M(m) => m.0 as usize,
+            Player::W(w) => self.men as usize + w.0 as usize,
+        }
+    }
+
     /// Iterator over all men.
     pub fn men_iter(&self) -> impl Iterator<Item = Man> {
         (0..self.men).map(Man)
@@ -267,3 +370,125 @@ impl Roster {
         (2 * games).saturating_sub(self.distinct_pairs())
     }
 }
+
+/// Courts offered round by round: `per_round[i]` in round `i`, and the last
+/// entry in every round after. Courts free up and get taken over an evening,
+/// so `[2, 2, 2, 4]` is two courts for three rounds and four from then on. A
+/// bare court count converts to the plan with that many courts throughout.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CourtPlan {
+    per_round: Vec<u16>,
+}
+
+impl CourtPlan {
+    /// The plan `per_round`, its last entry repeating. Panics if it is empty.
+    pub fn new(per_round: Vec<u16>) -> Self {
+        assert!(!per_round.is_empty(), "a court plan needs at least one round");
+        CourtPlan { per_round }
+    }
+
+    /// `courts` courts in every round.
+    pub fn uniform(courts: u16) -> Self {
+        CourtPlan { per_round: vec![courts] }
+    }
+
+    /// Parse a plan like `"2x3,4"`: comma-separated court counts, each
+    /// optionally `COURTSxROUNDS`; the last count continues indefinitely.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let bad = || format!("court plan `{spec}` is not like `2x3,4` (courts × rounds, then courts)");
+        let mut per_round = Vec::new();
+        for item in spec.split(',').map(str::trim) {
+            let (courts, rounds) = item.split_once('x').unwrap_or((item, "1"));
+            let courts: u16 = courts.trim().parse().ok().filter(|&c| c > 0).ok_or_else(bad)?;
+            let rounds: usize = rounds.trim().parse().ok().filter(|&r| r > 0).ok_or_else(bad)?;
+            per_round.extend(std::iter::repeat_n(courts, rounds));
+        }
+        Ok(CourtPlan::new(per_round))
+    }
+
+    /// Courts offered in round `round` (0-based).
+    pub fn at(&self, round: usize) -> u16 {
+        *self.per_round.get(round).unwrap_or(&self.per_round[self.per_round.len() - 1])
+    }
+
+    /// The most courts any round offers.
+    pub fn most(&self) -> u16 {
+        self.per_round.iter().copied().max().unwrap_or(0)
+    }
+
+    /// The fewest courts any round offers.
+    pub fn fewest(&self) -> u16 {
+        self.per_round.iter().copied().min().unwrap_or(0)
+    }
+
+    /// Whether every round offers the same courts.
+    pub fn is_uniform(&self) -> bool {
+        self.fewest() == self.most()
+    }
+
+    /// The plan from round `rounds` on.
+    pub fn after(&self, rounds: usize) -> Self {
+        let rest = self.per_round.get(rounds..).filter(|r| !r.is_empty());
+        CourtPlan::new(rest.map_or_else(|| vec![self.at(rounds)], <[u16]>::to_vec))
+    }
+
+    /// Court-slots offered by the first `rounds` rounds.
+    pub fn slots(&self, rounds: usize) -> usize {
+        (0..rounds).map(|r| self.at(r) as usize).sum()
+    }
+
+    /// Games the first `rounds` rounds can hold on `roster`: a game per
+    /// court, as far as the roster fills them.
+    pub fn games(&self, roster: Roster, rounds: usize) -> usize {
+        let fill = (roster.men as usize / 2).min(roster.women as usize / 2);
+        (0..rounds).map(|r| (self.at(r) as usize).min(fill)).sum()
+    }
+
+    /// The fewest rounds that can hold `games` games on `roster` (`None` if
+    /// no number of rounds can).
+    pub fn rounds_for(&self, roster: Roster, games: usize) -> Option<usize> {
+        let fill = (roster.men as usize / 2).min(roster.women as usize / 2);
+        let last = (self.at(self.per_round.len()) as usize).min(fill);
+        let (mut rounds, mut held) = (0, 0);
+        while held < games {
+            if rounds >= self.per_round.len() {
+                return (last > 0).then(|| rounds + (games - held).div_ceil(last));
+            }
+            held += (self.at(rounds) as usize).min(fill);
+            rounds += 1;
+        }
+        Some(rounds)
+    }
+}
+
+impl From<u16> for CourtPlan {
+    fn from(courts: u16) -> Self {
+        CourtPlan::uniform(courts)
+    }
+}
+
+impl From<&CourtPlan> for CourtPlan {
+    fn from(plan: &CourtPlan) -> Self {
+        plan.clone()
+    }
+}
+
+impl fmt::Display for CourtPlan {
+    /// The [`parse`](CourtPlan::parse) form, runs collapsed: `2x3,4`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut runs: Vec<(u16, usize)> = Vec::new();
+        for &c in &self.per_round {
+            match runs.last_mut() {
+                Some((last, n)) if *last == c => *n += 1,
+                _ => runs.push((c, 1)),
+            }
+        }
+        let last = runs.len() - 1;
+        let items: Vec<String> = runs
+            .iter()
+            .enumerate()
+            .map(|(i, &(c, n))| if n == 1 || i == last { c.to_string() } else { format!("{c}x{n}") })
+            .collect();
+        write!(f, "{}", items.join(","))
+    }
+}
@@ -0,0 +1,139 @@
+//! Objective weights: what the optimizers trade off, and how hard.
+//!
+//! Every optimizer minimizes a weighted sum. Part 1 ([`search`](crate::search))
+//! keeps the game count maximal and weighs same-gender repeats, participation
+//! spread and — when choosing between candidates — the round count. Part 2
+//! ([`target`](crate::target)) scores each game it places by the repeats it
+//! would add, how much its players have already played, and how often its
+//! same-gender pairs have met — and, for a social mixer, how many of its
+//! players have already met at all. A night built for a
+//! [`Season`](crate::season::Season) also pays [`Objective::history`] for the
+//! partnerships and oppositions it repeats from earlier nights, and counts
+//! earlier same-gender meetings as repeats too.
+//!
+//! The defaults are the weights the optimizers were tuned with; the three
+//! emphasis presets differ only in [`Objective::rounds`], and
+//! [`Objective::mixer`] turns on [`Objective::coverage`]. Clubs tune the rest
+//! per event (CLI `weights=…`, wasm `weights` argument) without recompiling.
+//! The struct is `#[non_exhaustive]` so later metrics (rest, skill) can join
+//! it without breaking callers: start from a preset and assign fields.
+
+/// Emphasis presets for [`Objective::rounds`]: how much each extra round costs
+/// at candidate selection. Higher ⇒ fuller courts at the cost of more
+/// same-gender repeats. This is the one knob that slides along the Pareto
+/// frontier.
+pub const EMPHASIS_VARIETY: f64 = 0.0; // fewest repeats, courts may sit idle
+pub const EMPHASIS_BALANCED: f64 = 40.0; // sensible middle (default)
+pub const EMPHASIS_COURTS: f64 = 100_000.0; // full courts above all else
+
+/// Weights for every term the optimizers minimize. Magnitudes matter more
+/// than exact values: the defaults are spread orders of magnitude apart so
+/// the objective is effectively lexicographic.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct Objective {
+    /// Part 1: per meeting of the most-met man–man and woman–woman pairs
+    /// (avoid facing the same opponent 3× while others meet once).
+    pub max_meetings: f64,
+    /// Part 1: per same-gender meeting beyond the first, over all pairs.
+    pub excess: f64,
+    /// Part 1: per game of participation spread (most − fewest played).
+    pub spread: f64,
+    /// Part 1: per round, at candidate selection — the courts ⇄ variety
+    /// emphasis. Higher ⇒ fuller courts at the cost of more repeats.
+    pub rounds: f64,
+    /// Part 1 warm starts: per round of the starting schedule that is torn
+    /// up. Higher ⇒ closer to the schedule people already know.
+    pub churn: f64,
+    /// Part 2: per partnership or mixed opposition a game would repeat.
+    pub repeat: f64,
+    /// Part 2: per game its four players have already played (fair byes).
+    pub fairness: f64,
+    /// Part 2: per earlier meeting of a game's man–man and woman–woman pairs.
+    pub same_gender: f64,
+    /// Part 2: per pair of a game's players who have already met, as partners
+    /// or opponents of either gender. Non-zero also makes the annealing key
+    /// put people met (most, then evenest) right after hard repeats.
+    pub coverage: f64,
+    /// Both parts, for a night of a season: per time a game's partnerships
+    /// and mixed oppositions were already played on earlier nights.
+    pub history: f64,
+}
+
+impl Default for Objective {
+    fn default() -> Self {
+        Objective {
+            max_meetings: 1000.0,
+            excess: 10.0,
+            spread: 1.0,
+            rounds: EMPHASIS_BALANCED,
+            churn: 3.0,
+            repeat: 1_000_000.0,
+            fairness: 1_000.0,
+            same_gender: 1.0,
+            coverage: 0.0,
+            history: 100.0,
+        }
+    }
+}
+
+impl Objective {
+    /// The default weights with round weight `rounds`.
+    pub fn with_emphasis(rounds: f64) -> Self {
+        Objective { rounds, ..Objective::default() }
+    }
+
+    /// Full courts above all else.
+    pub fn courts() -> Self {
+        Objective::with_emphasis(EMPHASIS_COURTS)
+    }
+
+    /// The sensible middle (the default).
+    pub fn balanced() -> Self {
+        Objective::with_emphasis(EMPHASIS_BALANCED)
+    }
+
+    /// Fewest repeats; courts may sit idle.
+    pub fn variety() -> Self {
+        Objective::with_emphasis(EMPHASIS_VARIETY)
+    }
+
+    /// Part 2 social mixer: everyone meets as many different people as the
+    /// games allow, as evenly as possible. Fair byes still come first.
+    pub fn mixer() -> Self {
+        Objective { coverage: 100.0, ..Objective::balanced() }
+    }
+
+    /// These weights with overrides from a spec like
+    /// `"excess:20, rounds:5"`. Names are the field names with `-` or `_`
+    /// (`max-meetings`, `same-gender`); values must be finite and
+    /// non-negative.
+    pub fn with_overrides(mut self, spec: &str) -> Result<Self, String> {
+        for item in spec.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            let (name, value) = item
+                .split_once(':')
+                .ok_or_else(|| format!("weight `{item}` is not name:value"))?;
+            let value: f64 = value
+                .trim()
+                .parse()
+                .ok()
+                .filter(|v: &f64| v.is_finite() && *v >= 0.0)
+                .ok_or_else(|| format!("weight `{item}` needs a non-negative number"))?;
+            let field = match name.trim().replace('_', "-").as_str() {
+                "max-meetings" => &mut self.max_meetings,
+                "excess" => &mut self.excess,
+                "spread" => &mut self.spread,
+                "rounds" => &mut self.rounds,
+                "churn" => &mut self.churn,
+                "repeat" => &mut self.repeat,
+                "fairness" => &mut self.fairness,
+                "same-gender" => &mut self.same_gender,
+                "coverage" => &mut self.coverage,
+                "history" => &mut self.history,
+                other => return Err(format!("unknown weight `{other}`")),
+            };
+            *field = value;
+        }
+        Ok(self)
+    }
+}
@@ -0,0 +1,303 @@
+//! Exact repacking: the fewest rounds a fixed game set fits in, with proof.
+//!
+//! Packing games into rounds is colouring the *conflict graph* (two games
+//! conflict when they share a player) with at most `courts` games per colour.
+//! [`search`](crate::search)'s randomized repack is fast but may miss the
+//! minimum. [`pack_exact`] starts from it and runs a branch-and-bound over
+//! round counts from the [`lower_bound`] up: each count is either packed —
+//! and then, every smaller count having been ruled out, optimal — or refuted
+//! exhaustively, which raises the bound. A [`Budget`] and a work cap stop the
+//! search; the result then keeps the best packing and the best proven bound.
+//!
+//! The work cap keeps unlimited-budget runs deterministic, so the optimizers
+//! can use the packer without making results depend on machine speed.
+
+use crate::budget::Budget;
+use crate::model::{CourtPlan, Game, Player, Roster, Round, Schedule};
+use crate::search::repack;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Game-in-round checks per [`pack_exact`] call before it settles for what it
+/// has. Counting checks rather than nodes keeps large game sets as cheap to
+/// give up on as small ones.
+const MAX_WORK: u64 = 50_000_000;
+
+/// Why no packing of a game set can use fewer rounds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RoundBound {
+    /// `games` games, at most `per_round` of them per round (courts, or the
+    /// scarcer gender: a game takes two of each).
+    Capacity { games: usize, per_round: usize },
+    /// `player` is in `games` games and plays at most one per round.
+    Busiest { player: Player, games: usize },
+    /// Exhaustive search found no packing into `rounds − 1` rounds.
+    Exhausted { rounds: usize },
+}
+
+impl RoundBound {
+    /// The bound: every packing needs at least this many rounds.
+    pub fn rounds(&self) -> usize {
+        match *self {
+            RoundBound::Capacity { games, per_round } => games.div_ceil(per_round),
+            RoundBound::Busiest { games, .. } => games,
+            RoundBound::Exhausted { rounds } => rounds,
+        }
+    }
+}
+
+impl fmt::Display for RoundBound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            RoundBound::Capacity { games, per_round } => {
+                write!(f, "{} games at {} per round", games, per_round)
+            }
+            RoundBound::Busiest { player, games } => {
+                write!(f, "{} plays {} games, one per round", player, games)
+            }
+            RoundBound::Exhausted { rounds } => {
+                write!(f, "no packing into {} rounds exists", rounds - 1)
+            }
+        }
+    }
+}
+
+/// A packing of a game set and the best lower bound proven for it.
+#[derive(Clone, Debug)]
+pub struct Packing {
+    pub schedule: Schedule,
+    pub bound: RoundBound,
+}
+
+impl Packing {
+    /// The packing meets its bound: no packing of these games has fewer rounds.
+    pub fn is_optimal(&self) -> bool {
+        self.schedule.num_rounds() == self.bound.rounds()
+    }
+}
+
+/// The counting lower bounds on rounds for `games`: court/gender capacity and
+/// the busiest player, whichever is higher.
+pub fn lower_bound(games: &[Game], roster: Roster, courts: u16) -> RoundBound {
+    let per_round = per_round(roster, courts);
+    let capacity = RoundBound::Capacity { games: games.len(), per_round };
+    let mut played: HashMap<Player, usize> = HashMap::new();
+    for p in games.iter().flat_map(Game::players) {
+        *played.entry(p).or_insert(0) += 1;
+    }
+    match played.into_iter().max_by_key(|&(p, n)| (n, std::cmp::Reverse(p))) {
+        Some((player, games)) if games > capacity.rounds() => {
+            RoundBound::Busiest { player, games }
+        }
+        _ => capacity,
+    }
+}
+
+/// Repack the games of `schedule` (well-formed, on `roster`) into as few
+/// rounds of at most `courts` player-disjoint games as can be proven within
+/// `budget` and the work cap. `schedule`'s own rounds, if they fit, are the
+/// packing to beat, so the result never has more rounds. The packing is
+/// optimal when [`Packing::is_optimal`]; otherwise it is the best found and
+/// `bound` the best proven.
+pub fn pack_exact(schedule: &Schedule, roster: Roster, courts: u16, budget: &Budget) -> Packing {
+    let games: Vec<Game> = schedule.all_games().copied().collect();
+    let mut bound = lower_bound(&games, roster, courts);
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut best = repack(&games, &CourtPlan::uniform(courts), 50, budget, &mut rng);
+    let fits = schedule.rounds.iter().all(|r| r.games.len() <= courts as usize);
+    if fits && schedule.num_rounds() <= best.num_rounds() {
+        let rounds = schedule.rounds.iter().filter(|r| !r.games.is_empty());
+        best = Schedule::new(rounds.map(|r| Round::new(r.games.clone())).collect());
+    }
+    let mut search = Search::new(&games, roster, per_round(roster, courts));
+    let mut k = bound.rounds();
+    while k < best.num_rounds() {
+        match search.pack(k, budget) {
+            Outcome::Packed(rounds) => {
+                best = Schedule::new(rounds.into_iter().map(Round::new).collect());
+                break;
+            }
+            Outcome::Refuted => {
+                k += 1;
+                bound = RoundBound::Exhausted { rounds: k };
+            }
+            Outcome::GaveUp => break,
+        }
+    }
+    Packing { schedule: best, bound }
+}
+
+/// Games one round can hold: a court each, two men and two women each.
+fn per_round(roster: Roster, courts: u16) -> usize {
+    (courts as usize).min(roster.men as usize / 2).min(roster.women as usize / 2).max(1)
+}
+
+enum Outcome {
+    Packed(Vec<Vec<Game>>),
+    Refuted,
+    GaveUp,
+}
+
+/// Branch-and-bound state for "do these games fit in `k` rounds?". Players
+/// are bits (men first, then women); each round tracks the players it uses.
+struct Search<'a> {
+    games: &'a [Game],
+    masks: Vec<Vec<u64>>,
+    /// Games each player is in.
+    of_player: Vec<Vec<usize>>,
+    per_round: usize,
+    nodes: u64,
+    work: u64,
+}
+
+impl<'a> Search<'a> {
+    fn new(games: &'a [Game], roster: Roster, per_round: usize) -> Self {
+        let players = roster.total_players();
+        let words = players.div_ceil(64).max(1);
+        let bit = |p: Player| roster.seat(p);
+        let mut of_player = vec![Vec::new(); players];
+        let masks = games
+            .iter()
+            .enumerate()
+            .map(|(gi, g)| {
+                let mut mask = vec![0u64; words];
+                for p in g.players() {
+                    mask[bit(p) / 64] |= 1 << (bit(p) % 64);
+                    of_player[bit(p)].push(gi);
+                }
+                mask
+            })
+            .collect();
+        Search { games, masks, of_player, per_round, nodes: 0, work: 0 }
+    }
+
+    /// Pack into exactly `k` rounds, refute that, or give up on the budget.
+    fn pack(&mut self, k: usize, budget: &Budget) -> Outcome {
+        let words = self.masks.first().map_or(1, Vec::len);
+        let mut state = State {
+            used: vec![vec![0u64; words]; k],
+            size: vec![0; k],
+            round_of: vec![usize::MAX; self.games.len()],
+            open: 0,
+        };
+        match self.dfs(&mut state, self.games.len(), budget) {
+            Some(true) => {
+                let mut rounds = vec![Vec::new(); state.open];
+                for (gi, &r) in state.round_of.iter().enumerate() {
+                    rounds[r].push(self.games[gi]);
+                }
+                Outcome::Packed(rounds)
+            }
+            Some(false) => Outcome::Refuted,
+            None => Outcome::GaveUp,
+        }
+    }
+
+    /// Whether game `gi` fits round `r`.
+    fn fits(&self, s: &State, gi: usize, r: usize) -> bool {
+        s.size[r] < self.per_round
+            && self.masks[gi].iter().zip(&s.used[r]).all(|(a, b)| a & b == 0)
+    }
+
+    /// Rounds game `gi` may go in: the open ones it fits, plus the first
+    /// unopened one (rounds are interchangeable, so trying more is redundant).
+    fn options(&self, s: &State, gi: usize) -> Vec<usize> {
+        let mut out: Vec<usize> = (0..s.open).filter(|&r| self.fits(s, gi, r)).collect();
+        if s.open < s.size.len() {
+            out.push(s.open);
+        }
+        out
+    }
+
+    /// `Some(found)`, or `None` once the budget or work cap runs out.
+    fn dfs(&mut self, s: &mut State, left: usize, budget: &Budget) -> Option<bool> {
+        if left == 0 {
+            return Some(true);
+        }
+        self.nodes += 1;
+        self.work += (left * (s.open + 1)) as u64;
+        if self.work > MAX_WORK || (self.nodes.is_multiple_of(1024) && budget.expired()) {
+            return None;
+        }
+        if !self.may_fit(s, left) {
+            return Some(false);
+        }
+        // Most constrained game first (fewest rounds it can still go in).
+        let mut pick: Option<(usize, Vec<usize>)> = None;
+        for gi in (0..self.games.len()).filter(|&gi| s.round_of[gi] == usize::MAX) {
+            let opts = self.options(s, gi);
+            if opts.is_empty() {
+                return Some(false);
+            }
+            if pick.as_ref().is_none_or(|(_, best)| opts.len() < best.len()) {
+                pick = Some((gi, opts));
+            }
+        }
+        let (gi, opts) = pick.expect("an unplaced game");
+        for r in opts {
+            let opened = r == s.open;
+            s.place(gi, r, &self.masks[gi]);
+            if opened {
+                s.open += 1;
+            }
+            let found = self.dfs(s, left - 1, budget);
+            if found == Some(true) {
+                return found; // keep the placement: it is the answer
+            }
+            if opened {
+                s.open -= 1;
+            }
+            s.unplace(gi, r, &self.masks[gi]);
+            found?;
+        }
+        Some(false)
+    }
+
+    /// Counting check: enough free slots overall, and every player's unplaced
+    /// games still have as many rounds without that player.
+    fn may_fit(&self, s: &State, left: usize) -> bool {
+        let k = s.size.len();
+        let free: usize = s.size.iter().map(|&n| self.per_round - n).sum();
+        if left > free {
+            return false;
+        }
+        self.of_player.iter().enumerate().all(|(p, games)| {
+            let pending = games.iter().filter(|&&gi| s.round_of[gi] == usize::MAX).count();
+            if pending == 0 {
+                return true;
+            }
+            let (w, b) = (p / 64, 1u64 << (p % 64));
+            let open = (0..k).filter(|&r| s.used[r][w] & b == 0 && s.size[r] < self.per_round);
+            open.count() >= pending
+        })
+    }
+}
+
+/// A partial packing: players used and games placed per round.
+struct State {
+    used: Vec<Vec<u64>>,
+    size: Vec<usize>,
+    round_of: Vec<usize>,
+    /// Rounds holding at least one game; always a prefix.
+    open: usize,
+}
+
+impl State {
+    fn place(&mut self, gi: usize, r: usize, mask: &[u64]) {
+        for (u, m) in self.used[r].iter_mut().zip(mask) {
+            *u |= m;
+        }
+        self.size[r] += 1;
+        self.round_of[gi] = r;
+    }
+
+    fn unplace(&mut self, gi: usize, r: usize, mask: &[u64]) {
+        for (u, m) in self.used[r].iter_mut().zip(mask) {
+            *u &= !m;
+        }
+        self.size[r] -= 1;
+        self.round_of[gi] = usize::MAX;
+    }
+}
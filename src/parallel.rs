@@ -0,0 +1,111 @@
+//! Independent restarts spread across threads, reproducibly.
+//!
+//! The optimizers run many independent restarts (anneal starts, Part 2
+//! rebuilds) and keep the best. Each restart `i` gets its own RNG seeded by
+//! [`derive_seed`]`(seed, i)` rather than sharing one stream, and results are
+//! collected back in index order before the best is picked — so the answer
+//! for a given seed is the same on one thread or sixteen.
+//!
+//! Natively the restarts run on scoped threads (all cores by default, see
+//! [`set_threads`], or [`with_threads`] for one call); the wasm build has no
+//! threads and runs them in turn.
+
+use std::cell::Cell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Configured worker count; `0` means one per available core.
+static THREADS: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    /// A [`with_threads`] override on this thread, if one is running.
+    static SCOPED: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/// Cap the worker threads used by every optimizer (`0` = all cores, `1` =
+/// sequential). Affects speed only, never results.
+pub fn set_threads(n: usize) {
+    THREADS.store(n, Ordering::Relaxed);
+}
+
+/// Run `f` with the worker count set to `n` (as for [`set_threads`]) for
+/// this thread and the workers it starts, leaving every other caller on the
+/// global setting.
+pub fn with_threads<T>(n: usize, f: impl FnOnce() -> T) -> T {
+    /// Puts the previous override back, even if `f` panics.
+    struct Restore(Option<usize>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            SCOPED.with(|s| s.set(self.0));
+        }
+    }
+    let _restore = Restore(SCOPED.with(|s| s.replace(Some(n))));
+    f()
+}
+
+/// Worker threads to use for `jobs` independent jobs.
+pub fn workers(jobs: usize) -> usize {
+    let set = SCOPED.with(Cell::get).unwrap_or_else(|| THREADS.load(Ordering::Relaxed));
+    let configured = match set {
+        0 => available(),
+        n => n,
+    };
+    configured.min(jobs).max(1)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn available() -> usize {
+    std::thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn available() -> usize {
+    1
+}
+
+/// Seed for restart `index` of a run seeded with `seed` (SplitMix64 over the
+/// pair), so neighbouring restarts get unrelated streams.
+pub fn derive_seed(seed: u64, index: usize) -> u64 {
+    let mut z = seed ^ (index as u64 + 1).wrapping_mul(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Run `job(0) … job(jobs − 1)` on up to [`workers`] threads and return the
+/// results in index order. Jobs are handed out one at a time, so uneven jobs
+/// still balance.
+pub fn run_indexed<T, F>(jobs: usize, job: F) -> Vec<T>
+where
+    T: Send,
+    F: Fn(usize) -> T + Sync,
+{
+    let threads = workers(jobs);
+    if threads <= 1 || cfg!(target_arch = "wasm32") {
+        return (0..jobs).map(job).collect();
+    }
+    let scoped = SCOPED.with(Cell::get);
+    let next = AtomicUsize::new(0);
+    let mut done: Vec<(usize, T)> = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    SCOPED.with(|s| s.set(scoped));
+                    let mut out = Vec::new();
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        if i >= jobs {
+                            break out;
+                        }
+                        out.push((i, job(i)));
+                    }
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|h| h.join().expect("optimizer thread panicked"))
+            .collect()
+    });
+    done.sort_by_key(|&(i, _)| i);
+    done.into_iter().map(|(_, t)| t).collect()
+}
@@ -0,0 +1,206 @@
+//! Choosing a round's next game: the candidate scans the builders share.
+//!
+//! [`greedy`](crate::greedy) and [`search`](crate::search) fill a round court
+//! by court with a legal game of the fewest same-gender meetings, and
+//! [`target`](crate::target) with a game of least weighted cost; all break
+//! ties uniformly. Scanning every `{a, b} × {x, y}` is `O(M²W²)` per game.
+//! These scans visit man pairs only: the women each pair may play with
+//! without a repeat are an intersection of the ledger's
+//! [bitsets](crate::verify::LiveReport::unpartnered) ([`Sides`]), and the
+//! women pairs at each cost level are counted a word at a time, so a pick
+//! costs `O(M²·W·W/64)` per level and still draws uniformly from every tie.
+
+use crate::bits::Bits;
+use crate::model::{Game, Man, Roster, Team, Woman};
+use crate::verify::LiveReport;
+use rand::rngs::StdRng;
+use rand::Rng;
+
+/// Women pairs at one cost level, as rows: `level[x]` holds the women `y`
+/// whose pair with `x` is at the level. Symmetric, with empty diagonal.
+pub(crate) type Level = Vec<Bits>;
+
+/// Add pair `{x, y}` to the level at index `c`, growing `levels` to reach it.
+pub(crate) fn add_to_level(levels: &mut Vec<Level>, women: usize, c: usize, x: usize, y: usize) {
+    while levels.len() <= c {
+        levels.push(vec![Bits::new(women); women]);
+    }
+    levels[c][x].insert(y);
+    levels[c][y].insert(x);
+}
+
+/// The women a man pair `{a, b}` can play without repeating a partnership or
+/// a mixed opposition, restricted to the round's free women: `a` partners `x`
+/// and faces `y` for `x ∈ xs`, `y ∈ ys`.
+pub(crate) struct Sides {
+    xs: Bits,
+    ys: Bits,
+    both: Bits,
+}
+
+impl Sides {
+    pub(crate) fn new(women: usize) -> Self {
+        Sides { xs: Bits::new(women), ys: Bits::new(women), both: Bits::new(women) }
+    }
+
+    /// Load the women sides for men `a` and `b`: `a` partners `x` and faces
+    /// `y`, `b` partners `y` and faces `x`.
+    pub(crate) fn load(&mut self, led: &LiveReport, a: Man, b: Man, free: &Bits) {
+        self.xs.set_and3(led.unpartnered(a), led.unopposed(b), free);
+        self.ys.set_and3(led.unopposed(a), led.unpartnered(b), free);
+        self.both.set_and(&self.xs, &self.ys);
+    }
+
+    /// Load the women sides for men `a` and `b` that neither has met as a
+    /// partner or an opponent: any such pair is fresh either way round.
+    pub(crate) fn load_unmet(&mut self, led: &LiveReport, a: Man, b: Man, free: &Bits) {
+        self.ys.set_and3(led.unpartnered(a), led.unopposed(a), free);
+        self.both.set_and3(led.unpartnered(b), led.unopposed(b), &self.ys);
+        self.xs.set_and(&self.both, &self.both);
+        self.ys.set_and(&self.both, &self.both);
+    }
+
+    /// Narrow the loaded sides to games that repeat no partnership or mixed
+    /// opposition from an earlier night of the season, if `led` is a night
+    /// of one.
+    pub(crate) fn unplayed_before(&mut self, led: &LiveReport, a: Man, b: Man) {
+        let (Some((pa, oa)), Some((pb, ob))) = (led.fresh_before(a), led.fresh_before(b)) else {
+            return;
+        };
+        self.xs.and_with(pa, ob);
+        self.ys.and_with(oa, pb);
+        self.both.set_and(&self.xs, &self.ys);
+    }
+
+    /// Whether `{x, y}` is repeat-free in either orientation.
+    pub(crate) fn fresh(&self, x: usize, y: usize) -> bool {
+        (self.xs.contains(x) && self.ys.contains(y)) || (self.xs.contains(y) && self.ys.contains(x))
+    }
+
+    /// Repeat-free women pairs `{x, y}` in `level`. A pair fresh either way
+    /// round is counted once, as `x < y`.
+    pub(crate) fn count(&self, level: &[Bits]) -> usize {
+        self.xs
+            .iter()
+            .map(|x| {
+                let n = self.ys.count_and(&level[x]);
+                if self.both.contains(x) {
+                    n - self.both.count_and_below(&level[x], x)
+                } else {
+                    n
+                }
+            })
+            .sum()
+    }
+
+    /// The `r`-th pair [`count`](Self::count) counts, as the game of `a`
+    /// and `b` it makes.
+    pub(crate) fn nth(&self, level: &[Bits], a: Man, b: Man, mut r: usize) -> Game {
+        for x in self.xs.iter() {
+            let dup = self.both.contains(x);
+            for y in self.ys.iter().filter(|&y| level[x].contains(y)) {
+                if dup && y < x && self.both.contains(y) {
+                    continue;
+                }
+                if r == 0 {
+                    return Game::new(Team::new(a, Woman(x as u16)), Team::new(b, Woman(y as u16)));
+                }
+                r -= 1;
+            }
+        }
+        unreachable!("pick index past the candidate count")
+    }
+}
+
+/// Draw uniformly from weighted `hits`: `(weight, item)`, `None` if all
+/// weights are zero.
+pub(crate) fn draw<T>(hits: Vec<(usize, T)>, rng: &mut StdRng) -> Option<(usize, T)> {
+    let total: usize = hits.iter().map(|h| h.0).sum();
+    if total == 0 {
+        return None;
+    }
+    let mut r = rng.random_range(0..total);
+    for (n, item) in hits {
+        if r < n {
+            return Some((r, item));
+        }
+        r -= n;
+    }
+    unreachable!("draw past the total weight")
+}
+
+/// Candidate scanner for one Part 1 round. The women-pair meeting counts are
+/// read once, when the round's scan starts: games added later only touch
+/// players the round has used, whose counts no longer matter.
+pub(crate) struct Picker {
+    free_men: Bits,
+    free_women: Bits,
+    /// `met[c]`: the free women pairs that have met exactly `c` times.
+    met: Vec<Level>,
+    sides: Sides,
+}
+
+impl Picker {
+    /// A scan for a round whose games so far are `round` (already in `led`).
+    pub(crate) fn new(led: &LiveReport, roster: Roster, round: &[Game]) -> Self {
+        let (m, w) = (roster.men as usize, roster.women as usize);
+        let mut free_men = Bits::full(m);
+        let mut free_women = Bits::full(w);
+        for g in round {
+            free_men.remove(g.a.man.0 as usize);
+            free_men.remove(g.b.man.0 as usize);
+            free_women.remove(g.a.woman.0 as usize);
+            free_women.remove(g.b.woman.0 as usize);
+        }
+        let mut met: Vec<Level> = Vec::new();
+        let free: Vec<usize> = free_women.iter().collect();
+        for (i, &x) in free.iter().enumerate() {
+            for &y in &free[i + 1..] {
+                let c = led.women_met(Woman(x as u16), Woman(y as u16));
+                add_to_level(&mut met, w, c, x, y);
+            }
+        }
+        Picker { free_men, free_women, met, sides: Sides::new(w) }
+    }
+
+    /// Mark `g`'s players as used this round.
+    pub(crate) fn take(&mut self, g: &Game) {
+        self.free_men.remove(g.a.man.0 as usize);
+        self.free_men.remove(g.b.man.0 as usize);
+        self.free_women.remove(g.a.woman.0 as usize);
+        self.free_women.remove(g.b.woman.0 as usize);
+    }
+
+    /// A uniformly random legal game of the fewest same-gender meetings among
+    /// the free players, or `None` if no legal game is left.
+    pub(crate) fn pick(&mut self, led: &LiveReport, rng: &mut StdRng) -> Option<Game> {
+        let men: Vec<Man> = self.free_men.iter().map(|i| Man(i as u16)).collect();
+        let mut best = usize::MAX;
+        // Each man pair at the best cost so far, by its women-pair level.
+        let mut hits: Vec<(usize, (Man, Man, usize))> = Vec::new();
+        for (i, &a) in men.iter().enumerate() {
+            for &b in &men[i + 1..] {
+                let men_met = led.men_met(a, b);
+                if men_met > best {
+                    continue;
+                }
+                self.sides.load(led, a, b, &self.free_women);
+                let levels = self.met.len().min(best.saturating_sub(men_met).saturating_add(1));
+                for level in 0..levels {
+                    let n = self.sides.count(&self.met[level]);
+                    if n > 0 {
+                        if men_met + level < best {
+                            best = men_met + level;
+                            hits.clear();
+                        }
+                        hits.push((n, (a, b, level)));
+                        break;
+                    }
+                }
+            }
+        }
+        let (r, (a, b, level)) = draw(hits, rng)?;
+        self.sides.load(led, a, b, &self.free_women);
+        Some(self.sides.nth(&self.met[level], a, b, r))
+    }
+}
@@ -0,0 +1,182 @@
+//! Pins: content fixed before the optimizer starts.
+//!
+//! Some games are socially required ("the two club founders always play the
+//! opening game on court 1") and some rounds are already printed. A [`Pins`]
+//! set fixes single games to a round — and optionally a court — and locks
+//! whole rounds. [`optimize_pinned`](crate::search::optimize_pinned) builds
+//! around them (the ruin step never removes pinned content), and
+//! [`assign_courts_pinned`](crate::rotation::assign_courts_pinned) rotates
+//! courts without moving them.
+//!
+//! `Pins` is also a [`Rule`]: add it to
+//! [`verify_with`](crate::verify::verify_with) and every pin the schedule does
+//! not honour comes back as a violation.
+
+use crate::model::{Game, Round, Schedule};
+use crate::rules::Rule;
+use crate::verify::{Report, Slot, Violation};
+use std::collections::BTreeMap;
+
+/// One game fixed to a round (0-based), and to a court if `court` is set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Pin {
+    pub game: Game,
+    pub round: usize,
+    pub court: Option<u16>,
+}
+
+/// Pinned games and locked rounds. Built up with [`Pins::game`] and
+/// [`Pins::round`]; the default set pins nothing.
+#[derive(Clone, Debug, Default)]
+pub struct Pins {
+    games: Vec<Pin>,
+    rounds: BTreeMap<usize, Round>,
+}
+
+impl Pins {
+    pub fn new() -> Self {
+        Pins::default()
+    }
+
+    /// Also pin `game` to round `round` (0-based), on `court` if given.
+    pub fn game(mut self, game: Game, round: usize, court: Option<u16>) -> Self {
+        self.games.push(Pin { game, round, court });
+        self
+    }
+
+    /// Also lock round `index` (0-based) to exactly `round`, courts included.
+    pub fn round(mut self, index: usize, round: Round) -> Self {
+        self.rounds.insert(index, round);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.games.is_empty() && self.rounds.is_empty()
+    }
+
+    /// The rounds the pins reach: one past the last pinned or locked round.
+    pub fn span(&self) -> usize {
+        let games = self.games.iter().map(|p| p.round + 1).max().unwrap_or(0);
+        let rounds = self.rounds.keys().next_back().map_or(0, |&i| i + 1);
+        games.max(rounds)
+    }
+
+    pub fn is_locked(&self, index: usize) -> bool {
+        self.rounds.contains_key(&index)
+    }
+
+    /// Round `index` as the pins alone make it: the locked round, or the games
+    /// pinned there on their courts (court-less pins on the lowest free ones).
+    pub fn pinned_round(&self, index: usize) -> Round {
+        if let Some(round) = self.rounds.get(&index) {
+            return round.clone();
+        }
+        let pins: Vec<&Pin> = self.games.iter().filter(|p| p.round == index).collect();
+        let games = pins.iter().map(|p| p.game).collect();
+        let requested: Vec<Option<u16>> = pins.iter().map(|p| p.court).collect();
+        Round::with_courts(games, fill_courts(&requested))
+    }
+
+    /// Every round `0..span()` as the pins alone make it. [`verify`] on this
+    /// tells whether the pins can be honoured at all (no clashes, no repeats).
+    ///
+    /// [`verify`]: crate::verify::verify
+    pub fn skeleton(&self) -> Schedule {
+        Schedule::new((0..self.span()).map(|i| self.pinned_round(i)).collect())
+    }
+
+    /// Round `index` with `games` played in it — its pinned games first, in
+    /// [`pinned_round`](Self::pinned_round) order — on courts that honour the
+    /// pins. A locked round is returned as locked.
+    pub fn place(&self, index: usize, games: Vec<Game>) -> Round {
+        if let Some(round) = self.rounds.get(&index) {
+            return round.clone();
+        }
+        let pinned = self.pinned_round(index);
+        let mut requested: Vec<Option<u16>> = pinned.courts.iter().copied().map(Some).collect();
+        requested.resize(games.len(), None);
+        Round::with_courts(games, fill_courts(&requested))
+    }
+
+    /// The court each game of `round` (round `index`) must stay on, if any.
+    pub fn fixed_courts(&self, index: usize, round: &Round) -> Vec<Option<u16>> {
+        let locked = self.rounds.get(&index);
+        round
+            .games
+            .iter()
+            .map(|g| match locked {
+                Some(l) => (0..l.games.len())
+                    .find(|&i| l.games[i].canonical() == g.canonical())
+                    .map(|i| l.court(i)),
+                None => self
+                    .games
+                    .iter()
+                    .find(|p| p.round == index && p.game.canonical() == g.canonical())
+                    .and_then(|p| p.court),
+            })
+            .collect()
+    }
+}
+
+/// Courts for games with optional requests: each request is kept, the rest
+/// take the lowest courts nobody requested, in order.
+fn fill_courts(requested: &[Option<u16>]) -> Vec<u16> {
+    let mut next = 0u16;
+    requested
+        .iter()
+        .map(|r| {
+            r.unwrap_or_else(|| {
+                while requested.contains(&Some(next)) {
+                    next += 1;
+                }
+                next += 1;
+                next - 1
+            })
+        })
+        .collect()
+}
+
+/// Same games on the same courts, in any order.
+fn same_round(a: &Round, b: &Round) -> bool {
+    let key = |r: &Round| {
+        let mut k: Vec<(u16, Game)> =
+            (0..r.games.len()).map(|i| (r.court(i), r.games[i].canonical())).collect();
+        k.sort();
+        k
+    };
+    key(a) == key(b)
+}
+
+impl Rule for Pins {
+    fn name(&self) -> &'static str {
+        "pins"
+    }
+
+    fn apply(&self, schedule: &Schedule, report: &mut Report) {
+        for pin in &self.games {
+            let wanted = pin.game.canonical();
+            let placed = schedule.rounds.get(pin.round).and_then(|r| {
+                let i = (0..r.games.len()).find(|&i| r.games[i].canonical() == wanted)?;
+                Some((r, i))
+            });
+            let (message, at) = match placed {
+                None => (format!("{} is pinned to round {}", pin.game, pin.round + 1), Vec::new()),
+                Some((r, i)) => match pin.court {
+                    Some(c) if r.court(i) != c => (
+                        format!("{} is pinned to court {}", pin.game, c + 1),
+                        vec![Slot::of(r, pin.round, i)],
+                    ),
+                    _ => continue,
+                },
+            };
+            report.violations.push(Violation::Rule { rule: self.name(), message, at });
+        }
+        for (&index, locked) in &self.rounds {
+            if !schedule.rounds.get(index).is_some_and(|r| same_round(r, locked)) {
+                let message = format!("round {} is locked and was changed", index + 1);
+                let at = Vec::new();
+                report.violations.push(Violation::Rule { rule: self.name(), message, at });
+            }
+        }
+    }
+}
@@ -0,0 +1,166 @@
+//! Per-player game quotas for Part 2.
+//!
+//! [`by_games_per_player`](crate::target::by_games_per_player) gives everyone
+//! the same `N`. Members differ — some come for three games, regulars want
+//! six — so a [`Quotas`] set gives each player a range of games.
+//! [`by_quotas`](crate::target::by_quotas) derives the game count from it and
+//! steers byes by it.
+//!
+//! `Quotas` is also a [`Rule`]: add it to
+//! [`verify_with`](crate::verify::verify_with) and every player whose games
+//! fall outside their quota comes back as a violation, with the games missed
+//! or overplayed in total as the `quota-miss` score.
+
+use crate::model::{Man, Player, Roster, Schedule, Woman};
+use crate::rules::Rule;
+use crate::verify::{Report, Violation};
+
+/// Games one player should play: at least `min`, at most `max`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Quota {
+    pub min: u32,
+    pub max: u32,
+}
+
+impl Quota {
+    /// Exactly `n` games.
+    pub fn exactly(n: u32) -> Self {
+        Quota { min: n, max: n }
+    }
+
+    /// Games short of `min` or past `max` after playing `played`.
+    pub fn miss(&self, played: usize) -> usize {
+        (self.min as usize).saturating_sub(played) + played.saturating_sub(self.max as usize)
+    }
+}
+
+/// A quota for every player on a roster. Built from a default with
+/// [`Quotas::new`], then overridden per player.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Quotas {
+    men: Vec<Quota>,
+    women: Vec<Quota>,
+}
+
+impl Quotas {
+    /// Everyone on `roster` gets `default`.
+    pub fn new(roster: Roster, default: Quota) -> Self {
+        Quotas { men: vec![default; roster.men as usize], women: vec![default; roster.women as usize] }
+    }
+
+    /// Every man gets `men`, every woman `women`.
+    pub fn by_gender(roster: Roster, men: Quota, women: Quota) -> Self {
+        Quotas { men: vec![men; roster.men as usize], women: vec![women; roster.women as usize] }
+    }
+
+    /// Also give `who` the quota `quota`. Players off the roster are ignored.
+    pub fn player(mut self, who: Player, quota: Quota) -> Self {
+        let slot = match who {
+            Player::M(m) => self.men.get_mut(m.0 as usize),
+            Player::W(w) => self.women.get_mut(w.0 as usize),
+        };
+        if let Some(q) = slot {
+            *q = quota;
+        }
+        self
+    }
+
+    /// Parse a spec like `"4-6, M1:3, W2:6-8"`: the entry without a player
+    /// is everyone's default (required), the rest override one player each.
+    /// Players are named as printed (`M1` is the first man); a quota is `N`
+    /// or `MIN-MAX`.
+    pub fn parse(roster: Roster, spec: &str) -> Result<Self, String> {
+        let items: Vec<&str> = spec.split(',').map(str::trim).filter(|s| !s.is_empty()).collect();
+        let default = items
+            .iter()
+            .find(|i| !i.contains(':'))
+            .ok_or_else(|| format!("quotas `{spec}` need a default, e.g. `4-6`"))?;
+        let mut quotas = Quotas::new(roster, parse_quota(default)?);
+        for item in items.iter().filter(|i| i.contains(':')) {
+            let (who, quota) = item.split_once(':').expect("filtered on ':'");
+            let who = parse_player(roster, who.trim())?;
+            quotas = quotas.player(who, parse_quota(quota)?);
+        }
+        Ok(quotas)
+    }
+
+    /// Whether these are quotas for exactly `roster`'s players.
+    pub fn fits(&self, roster: Roster) -> bool {
+        self.men.len() == roster.men as usize && self.women.len() == roster.women as usize
+    }
+
+    pub fn of(&self, who: Player) -> Quota {
+        match who {
+            Player::M(m) => self.men[m.0 as usize],
+            Player::W(w) => self.women[w.0 as usize],
+        }
+    }
+
+    /// The most games every quota allows at once. Each game seats two men and
+    /// two women, so it is half the smaller of the two genders' summed maxima.
+    /// Errors if the quotas contradict themselves: a `min` above its `max`,
+    /// or more games owed to one gender's minima than the other's maxima
+    /// allow.
+    pub fn total_games(&self) -> Result<usize, String> {
+        if let Some(who) = self.players().find(|&p| self.of(p).min > self.of(p).max) {
+            return Err(format!("{who}'s quota has its minimum above its maximum"));
+        }
+        let sum = |qs: &[Quota], f: fn(&Quota) -> u32| qs.iter().map(|q| f(q) as usize).sum::<usize>();
+        let most = sum(&self.men, |q| q.max).min(sum(&self.women, |q| q.max)) / 2;
+        let least = sum(&self.men, |q| q.min).max(sum(&self.women, |q| q.min)).div_ceil(2);
+        if least > most {
+            return Err(format!("the quotas need at least {least} games but allow at most {most}"));
+        }
+        Ok(most)
+    }
+
+    fn players(&self) -> impl Iterator<Item = Player> + '_ {
+        let men = (0..self.men.len()).map(|i| Player::M(Man(i as u16)));
+        men.chain((0..self.women.len()).map(|i| Player::W(Woman(i as u16))))
+    }
+}
+
+fn parse_quota(s: &str) -> Result<Quota, String> {
+    let n = |v: &str| v.trim().parse::<u32>().map_err(|_| format!("quota `{s}` is not N or MIN-MAX"));
+    match s.split_once('-') {
+        Some((min, max)) => Ok(Quota { min: n(min)?, max: n(max)? }),
+        None => Ok(Quota::exactly(n(s)?)),
+    }
+}
+
+fn parse_player(roster: Roster, s: &str) -> Result<Player, String> {
+    let bad = || format!("`{s}` is not a player on this roster (M1…M{}, W1…W{})", roster.men, roster.women);
+    let (kind, n) = s.split_at_checked(1).ok_or_else(bad)?;
+    let n: u16 = n.parse().ok().filter(|&n| n >= 1).ok_or_else(bad)?;
+    match kind {
+        "M" | "m" if n <= roster.men => Ok(Player::M(Man(n - 1))),
+        "W" | "w" if n <= roster.women => Ok(Player::W(Woman(n - 1))),
+        _ => Err(bad()),
+    }
+}
+
+impl Rule for Quotas {
+    fn name(&self) -> &'static str {
+        "quotas"
+    }
+
+    fn apply(&self, _schedule: &Schedule, report: &mut Report) {
+        let played = report.games_per_man.iter().chain(&report.games_per_woman);
+        let mut missed = 0;
+        for (who, &games) in self.players().zip(played) {
+            let quota = self.of(who);
+            if quota.miss(games) == 0 {
+                continue;
+            }
+            missed += quota.miss(games);
+            let wanted = if quota.min == quota.max {
+                quota.min.to_string()
+            } else {
+                format!("{}–{}", quota.min, quota.max)
+            };
+            let message = format!("{who} played {games} games, quota {wanted}");
+            report.violations.push(Violation::Rule { rule: self.name(), message, at: Vec::new() });
+        }
+        report.rule_scores.push(("quota-miss".to_string(), missed as f64));
+    }
+}
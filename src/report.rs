@@ -1,11 +1,17 @@
 //! Human-readable output: the round-by-round court grid and the quality report.
 
-use crate::model::{Player, Roster, Schedule};
-use crate::verify::Report;
+use crate::diff::ScheduleDiff;
+use crate::duty::duty_counts;
+use crate::model::{Player, Roster, Round, Schedule, SideGame};
+use crate::season::SeasonReport;
+use crate::session::{clock, Session};
+use crate::verify::{Report, Violation};
 use std::cmp::max;
 use std::collections::HashSet;
 
-/// Print the schedule as a `Round × Court` grid with a byes column.
+/// Print the schedule as a `Round × Court` grid with a byes column. Side
+/// games sit on their spare courts, and a round's drill heads its byes. A
+/// game's official follows it in its cell, with a duty tally under the grid.
 pub fn print_schedule(schedule: &Schedule, roster: Roster, courts: u16) {
     let courts = courts as usize;
 
@@ -21,15 +27,23 @@ pub fn print_schedule(schedule: &Schedule, roster: Roster, courts: u16) {
     let mut bye_strings = Vec::with_capacity(schedule.rounds.len());
 
     for round in &schedule.rounds {
-        let active: HashSet<Player> = round.active_players().into_iter().collect();
-        let byes: Vec<String> = everyone
+        let mut active: HashSet<Player> = round.active_players().into_iter().collect();
+        active.extend(round.side.iter().flat_map(SideGame::players));
+        let mut byes: Vec<String> = round
+            .side
             .iter()
-            .filter(|p| !active.contains(p))
-            .map(|p| p.to_string())
+            .filter(|s| s.court().is_none())
+            .map(SideGame::to_string)
             .collect();
-        for (j, game) in round.games.iter().enumerate() {
-            if j < court_widths.len() {
-                court_widths[j] = max(court_widths[j], game.to_string().len());
+        byes.extend(everyone.iter().filter(|p| !active.contains(p)).map(|p| p.to_string()));
+        for j in 0..round.games.len() {
+            if let Some(w) = court_widths.get_mut(round.court(j) as usize) {
+                *w = max(*w, game_cell(round, j).len());
+            }
+        }
+        for side in &round.side {
+            if let Some(w) = side.court().and_then(|c| court_widths.get_mut(c as usize)) {
+                *w = max(*w, side.to_string().len());
             }
         }
         bye_strings.push(if byes.is_empty() { "-".to_string() } else { byes.join(", ") });
@@ -54,14 +68,36 @@ pub fn print_schedule(schedule: &Schedule, roster: Roster, courts: u16) {
     // Body.
     for (i, round) in schedule.rounds.iter().enumerate() {
         print!("{:<6} ", i + 1);
-        for j in 0..courts {
-            match round.games.get(j) {
-                Some(g) => print!("{:<width$} ", g.to_string(), width = court_widths[j]),
-                None => print!("{:<width$} ", "", width = court_widths[j]),
+        for (j, &width) in court_widths.iter().enumerate() {
+            let game = (0..round.games.len()).find(|&k| round.court(k) == j as u16);
+            match (game, round.side_on_court(j as u16)) {
+                (Some(k), _) => print!("{:<width$} ", game_cell(round, k), width = width),
+                (None, Some(s)) => print!("{:<width$} ", s.to_string(), width = width),
+                (None, None) => print!("{:<width$} ", "", width = width),
             }
         }
         println!("{:<width$}", bye_strings[i], width = bye_width);
     }
+
+    let covered: usize = schedule.rounds.iter().map(|r| r.officials.iter().flatten().count()).sum();
+    if covered > 0 {
+        let duties = duty_counts(schedule, roster);
+        let (min, max) = (duties.iter().min().unwrap_or(&0), duties.iter().max().unwrap_or(&0));
+        let games: usize = schedule.rounds.iter().map(|r| r.games.len()).sum();
+        println!();
+        println!(
+            "Officials: {} of {} games covered, {}–{} duties per player",
+            covered, games, min, max
+        );
+    }
+}
+
+/// Game `i` of `round` as a grid cell, with its official if it has one.
+fn game_cell(round: &Round, i: usize) -> String {
+    match round.official(i) {
+        Some(p) => format!("{} (ref {})", round.games[i], p),
+        None => round.games[i].to_string(),
+    }
 }
 
 /// Print the verifier's quality report: legality, distance from the game
@@ -70,7 +106,7 @@ pub fn print_report(report: &Report) {
     println!();
     println!(
         "Roster: {} men × {} women   |   {} courts",
-        report.roster.men, report.roster.women, report.courts
+        report.roster.men, report.roster.women, report.court_plan
     );
     println!("{}", "=".repeat(56));
 
@@ -79,13 +115,19 @@ pub fn print_report(report: &Report) {
     if !report.is_structurally_valid() {
         println!("Structure:        INVALID");
         for v in &report.violations {
-            println!("  ! {:?}", v);
+            println!("  ! {}", v);
         }
     } else if report.is_legal() {
         println!("Legality:         LEGAL (no partnership or opponent repeats)");
     } else {
         println!("Structure:        valid (once-rules relaxed — see repeats below)");
     }
+    // Club rules (pins, quotas) are listed whatever the structure.
+    if report.is_structurally_valid() {
+        for v in report.violations.iter().filter(|v| matches!(v, Violation::Rule { .. })) {
+            println!("  ! {}", v);
+        }
+    }
 
     println!(
         "Games:            {} / {} max{}",
@@ -110,8 +152,10 @@ pub fn print_report(report: &Report) {
         );
     }
     println!(
-        "Rounds:           {}   (court utilization {:.0}%)",
+        "Rounds:           {} (floor {} for these games){}   court utilization {:.0}%",
         report.rounds,
+        report.round_floor,
+        if report.rounds == report.round_floor { " ✓" } else { "" },
         report.court_utilization * 100.0
     );
     println!(
@@ -136,6 +180,29 @@ pub fn print_report(report: &Report) {
         max_p,
         report.participation_spread()
     );
+    let met = report.met_per_man.iter().chain(&report.met_per_woman);
+    let (min_m, max_m) = (met.clone().min().unwrap_or(&0), met.max().unwrap_or(&0));
+    println!("People met:       {}–{} per player   (spread {})", min_m, max_m, report.met_spread());
+    if report.courts > 1 {
+        println!(
+            "Court rotation:   worst player {} games apart between courts",
+            report.court_imbalance()
+        );
+    }
+    if report.side_games + report.drill_slots > 0 {
+        let all = report.side_games_per_man.iter().chain(&report.side_games_per_woman);
+        let (min, max) = (all.clone().min().unwrap_or(&0), all.max().unwrap_or(&0));
+        println!(
+            "Side games:       {} ({}–{} per player, {} repeat meetings), {} drill slots",
+            report.side_games, min, max, report.side_repeat_excess, report.drill_slots
+        );
+    }
+    if report.optimal {
+        println!("Optimal:          ✓ proven — no legal schedule does better");
+    }
+    for (name, value) in &report.rule_scores {
+        println!("{:<17} {}", format!("{}:", name), value);
+    }
     println!("{}", "=".repeat(56));
 }
 
@@ -148,3 +215,164 @@ fn participation_range(report: &Report) -> (usize, usize) {
     let max = all.copied().max().unwrap_or(0);
     (min, max)
 }
+
+/// Print the Pareto frontier as a menu of options, one row each: rounds and
+/// court use against same-gender repeats and participation spread.
+pub fn print_frontier(options: &[(Schedule, Report)]) {
+    println!();
+    println!(
+        "{:<7} {:>6} {:>9} {:>12} {:>8}",
+        "Option", "Rounds", "Court use", "Same-gender", "Spread"
+    );
+    println!("{}", "=".repeat(56));
+    for (i, (_, r)) in options.iter().enumerate() {
+        println!(
+            "{:<7} {:>6} {:>8.0}% {:>12} {:>8}",
+            i + 1,
+            r.rounds,
+            r.court_utilization * 100.0,
+            r.man_repeat_excess + r.woman_repeat_excess,
+            r.participation_spread()
+        );
+    }
+    println!("{}", "=".repeat(56));
+}
+
+/// Print the projected start and end of each round `session` holds, with
+/// the break (if taken) between the rounds it separates.
+pub fn print_timetable(session: &Session) {
+    let times = session.round_times();
+    println!();
+    println!("{:<7} {:>6} {:>6}", "Round", "Start", "End");
+    println!("{}", "=".repeat(21));
+    for (i, &(start, end)) in times.iter().enumerate() {
+        if session.break_before() == Some(i) {
+            if let Some(b) = session.pause {
+                let from = clock(session, start - b.length);
+                println!("{:<7} {:>6} {:>6}", "break", from, clock(session, start));
+            }
+        }
+        println!("{:<7} {:>6} {:>6}", i + 1, clock(session, start), clock(session, end));
+    }
+    println!("{}", "=".repeat(21));
+    println!(
+        "{} rounds in {}–{}",
+        times.len(),
+        clock(session, 0),
+        clock(session, session.length)
+    );
+}
+
+/// Print the season so far: totals over every recorded night, then each
+/// man's partners still to come.
+pub fn print_season(report: &SeasonReport) {
+    println!();
+    println!(
+        "Season: {} night{}   |   {} men × {} women",
+        report.nights,
+        if report.nights == 1 { "" } else { "s" },
+        report.roster.men,
+        report.roster.women
+    );
+    println!("{}", "=".repeat(56));
+    let range = |v: &[usize]| {
+        let (lo, hi) = (v.iter().min(), v.iter().max());
+        format!("{}–{}", lo.copied().unwrap_or(0), hi.copied().unwrap_or(0))
+    };
+    println!(
+        "Games per man:    {}   per woman {}",
+        range(&report.games_per_man),
+        range(&report.games_per_woman)
+    );
+    println!("Partner repeats:  {} extra", report.partner_repeats);
+    println!("Opponent repeats: {} extra   (mixed man vs woman)", report.mixed_repeats);
+    println!("Man–man repeats:  {} extra", report.man_repeats);
+    println!("Woman–woman rpts: {} extra", report.woman_repeats);
+    println!("Never partnered:  {} pairs", report.never_partnered.len());
+    for m in report.roster.men_iter() {
+        let women: Vec<String> =
+            report.never_partnered.iter().filter(|p| p.0 == m).map(|p| p.1.to_string()).collect();
+        if !women.is_empty() {
+            println!("  {:<4} {}", m.to_string(), women.join(", "));
+        }
+    }
+    println!("{}", "=".repeat(56));
+}
+
+/// Print a schedule comparison: key metrics side by side, then the games
+/// added, removed and moved, then the players whose partners or opponents
+/// changed.
+pub fn print_diff(diff: &ScheduleDiff) {
+    let (a, b) = (&diff.before, &diff.after);
+    println!();
+    println!("{:<20} {:>8} {:>8} {:>8}", "Comparison", "before", "after", "Δ");
+    println!("{}", "=".repeat(56));
+    let rows: [(&str, usize, usize); 8] = [
+        ("Games", a.games, b.games),
+        ("Rounds", a.rounds, b.rounds),
+        ("Partner repeats", a.partner_repeat_excess, b.partner_repeat_excess),
+        ("Opponent repeats", a.mixed_repeat_excess, b.mixed_repeat_excess),
+        ("Man–man repeats", a.man_repeat_excess, b.man_repeat_excess),
+        ("Woman–woman rpts", a.woman_repeat_excess, b.woman_repeat_excess),
+        (
+            "Worst pair meets",
+            a.man_max_meetings.max(a.woman_max_meetings),
+            b.man_max_meetings.max(b.woman_max_meetings),
+        ),
+        ("Participation sprd", a.participation_spread(), b.participation_spread()),
+    ];
+    for (label, x, y) in rows {
+        println!("{:<20} {:>8} {:>8} {:>+8}", label, x, y, y as i64 - x as i64);
+    }
+    println!(
+        "{:<20} {:>7.0}% {:>7.0}% {:>+7.0}%",
+        "Court utilization",
+        a.court_utilization * 100.0,
+        b.court_utilization * 100.0,
+        (b.court_utilization - a.court_utilization) * 100.0
+    );
+    println!("{}", "=".repeat(56));
+
+    if diff.is_identical() {
+        println!("Same games in the same rounds.");
+        return;
+    }
+    println!(
+        "Games: {} unchanged, {} moved, {} removed, {} added",
+        diff.unchanged(),
+        diff.moved.len(),
+        diff.removed.len(),
+        diff.added.len()
+    );
+    for (r, g) in &diff.removed {
+        println!("  - round {:<3} {}", r + 1, g);
+    }
+    for (r, g) in &diff.added {
+        println!("  + round {:<3} {}", r + 1, g);
+    }
+    for m in &diff.moved {
+        println!("  ~ round {} → {}   {}", m.from_round + 1, m.to_round + 1, m.game);
+    }
+
+    if !diff.players.is_empty() {
+        println!("Players with changed partners/opponents: {}", diff.players.len());
+        for p in &diff.players {
+            let mut parts = Vec::new();
+            for (sign, label, list) in [
+                ('+', "partner", &p.partners_gained),
+                ('-', "partner", &p.partners_lost),
+                ('+', "opp", &p.opponents_gained),
+                ('-', "opp", &p.opponents_lost),
+            ] {
+                if !list.is_empty() {
+                    parts.push(format!("{}{} {}", sign, label, join(list)));
+                }
+            }
+            println!("  {:<4} {}", p.who.to_string(), parts.join("   "));
+        }
+    }
+}
+
+fn join(players: &[Player]) -> String {
+    players.iter().map(|p| p.to_string()).collect::<Vec<_>>().join(",")
+}
@@ -0,0 +1,317 @@
+//! Court rotation: decide which court each game is played on.
+//!
+//! The optimizers choose *which* games make up each round; which physical
+//! court a game lands on is a separate, later decision. Courts are rarely
+//! equal (one by the wall, one with a bad net), so [`assign_courts`] spreads
+//! every player across the courts as evenly as it can, without touching the
+//! game set or the round structure.
+//!
+//! The objective is `Σ count[player][court]²` — convex, so it pushes every
+//! player's court counts toward equal. A greedy round-by-round placement seeds
+//! it, then sweeps re-place one round at a time exactly (an assignment problem)
+//! until no round can improve. Deterministic: no randomness involved.
+//!
+//! [`assign_courts_pinned`] does the same around [`Pins`]: a game pinned to a
+//! court, or in a locked round, never leaves its court.
+//!
+//! Under a [`CourtPlan`] a round only uses the courts it offers, numbered
+//! from the first: a round with two courts plays on courts 1 and 2.
+
+use crate::model::{CourtPlan, Game, Player, Roster, Round, Schedule};
+use crate::pins::Pins;
+
+/// How often each player has been on each court so far.
+struct Counts {
+    roster: Roster,
+    courts: usize,
+    count: Vec<usize>,
+}
+
+impl Counts {
+    fn idx(&self, p: Player, c: u16) -> usize {
+        self.roster.seat(p) * self.courts + c as usize
+    }
+
+    fn get(&self, p: Player, c: u16) -> usize {
+        self.count[self.idx(p, c)]
+    }
+
+    fn place(&mut self, g: &Game, c: u16) {
+        for p in g.players() {
+            let i = self.idx(p, c);
+            self.count[i] += 1;
+        }
+    }
+
+    fn unplace(&mut self, g: &Game, c: u16) {
+        for p in g.players() {
+            let i = self.idx(p, c);
+            self.count[i] -= 1;
+        }
+    }
+
+    /// Cost of putting `g` on court `c`: how often its players have been there.
+    fn cost(&self, g: &Game, c: u16) -> usize {
+        g.players().iter().map(|&p| self.get(p, c)).sum()
+    }
+}
+
+/// Cost that keeps a game with a fixed court off every other court.
+const OFF_PIN: i64 = 1 << 40;
+
+/// Greedily place one round's games on its `open` courts: games with a
+/// `fixed` court go there, then repeatedly take the cheapest (game, free
+/// court) pair.
+fn place_round(cnt: &mut Counts, games: &[Game], fixed: &[Option<u16>], open: u16) -> Vec<u16> {
+    let mut court_of = vec![u16::MAX; games.len()];
+    let mut free: Vec<u16> = (0..open).collect();
+    for (gi, &c) in fixed.iter().enumerate() {
+        if let Some(c) = c {
+            court_of[gi] = c;
+            free.retain(|&f| f != c);
+            cnt.place(&games[gi], c);
+        }
+    }
+    for _ in fixed.iter().filter(|c| c.is_none()) {
+        let mut best: Option<(usize, usize, usize)> = None; // (cost, game, free idx)
+        for (gi, g) in games.iter().enumerate() {
+            if court_of[gi] != u16::MAX {
+                continue;
+            }
+            for (fi, &c) in free.iter().enumerate() {
+                let cost = cnt.cost(g, c);
+                if best.is_none_or(|(bc, _, _)| cost < bc) {
+                    best = Some((cost, gi, fi));
+                }
+            }
+        }
+        let (_, gi, fi) = best.expect("at least as many courts as games");
+        let c = free.remove(fi);
+        court_of[gi] = c;
+        cnt.place(&games[gi], c);
+    }
+    court_of
+}
+
+/// Minimum-cost assignment of rows to distinct columns (`rows ≤ cols`), by the
+/// Hungarian algorithm in `O(rows² · cols)`. Returns the column of each row.
+pub(crate) fn min_cost_assignment(cost: &[Vec<i64>]) -> Vec<usize> {
+    let n = cost.len();
+    let m = cost.first().map_or(0, Vec::len);
+    // 1-based potentials and matching, per the classic formulation.
+    let (mut u, mut v) = (vec![0i64; n + 1], vec![0i64; m + 1]);
+    let mut row_of = vec![0usize; m + 1]; // column → row (0 = free)
+    let mut way = vec![0usize; m + 1];
+    for i in 1..=n {
+        row_of[0] = i;
+        let mut j0 = 0;
+        let mut minv = vec![i64::MAX; m + 1];
+        let mut used = vec![false; m + 1];
+        loop {
+            used[j0] = true;
+            let i0 = row_of[j0];
+            let (mut delta, mut j1) = (i64::MAX, 0);
+            for j in 1..=m {
+                if !used[j] {
+                    let cur = cost[i0 - 1][j - 1] - u[i0] - v[j];
+                    if cur < minv[j] {
+                        minv[j] = cur;
+                        way[j] = j0;
+                    }
+                    if minv[j] < delta {
+                        delta = minv[j];
+                        j1 = j;
+                    }
+                }
+            }
+            for j in 0..=m {
+                if used[j] {
+                    u[row_of[j]] += delta;
+                    v[j] -= delta;
+                } else {
+                    minv[j] -= delta;
+                }
+            }
+            j0 = j1;
+            if row_of[j0] == 0 {
+                break;
+            }
+        }
+        loop {
+            let j1 = way[j0];
+            row_of[j0] = row_of[j1];
+            j0 = j1;
+            if j0 == 0 {
+                break;
+            }
+        }
+    }
+    let mut col_of = vec![0usize; n];
+    for j in 1..=m {
+        if row_of[j] != 0 {
+            col_of[row_of[j] - 1] = j - 1;
+        }
+    }
+    col_of
+}
+
+/// One improvement sweep: lift each round out in turn and re-place its games
+/// optimally against every other round. Players within a round are disjoint,
+/// so the change in `Σ count²` is linear in the placement — an assignment
+/// problem, solved exactly. Returns whether anything changed.
+fn sweep(
+    cnt: &mut Counts,
+    rounds: &mut [(Vec<Game>, Vec<u16>)],
+    fixed: &[Vec<Option<u16>>],
+    open: &[u16],
+) -> bool {
+    let mut improved = false;
+    for (((games, court_of), fixed), &open) in rounds.iter_mut().zip(fixed).zip(open) {
+        if games.is_empty() {
+            continue;
+        }
+        for (g, &c) in games.iter().zip(court_of.iter()) {
+            cnt.unplace(g, c);
+        }
+        // Placing g on c adds Σ (2·count + 1) over its players.
+        let cost: Vec<Vec<i64>> = games
+            .iter()
+            .zip(fixed)
+            .map(|(g, f)| {
+                (0..open)
+                    .map(|c| match f {
+                        Some(f) if *f != c => OFF_PIN,
+                        _ => (2 * cnt.cost(g, c) + 4) as i64,
+                    })
+                    .collect()
+            })
+            .collect();
+        let best = min_cost_assignment(&cost);
+        let old: i64 = court_of.iter().enumerate().map(|(i, &c)| cost[i][c as usize]).sum();
+        let new: i64 = best.iter().enumerate().map(|(i, &c)| cost[i][c]).sum();
+        if new < old {
+            for (c, &b) in court_of.iter_mut().zip(&best) {
+                *c = b as u16;
+            }
+            improved = true;
+        }
+        for (g, &c) in games.iter().zip(court_of.iter()) {
+            cnt.place(g, c);
+        }
+    }
+    improved
+}
+
+impl Counts {
+    /// Quality of the current placement: worst per-player court imbalance,
+    /// then `Σ count²`. Lower is better.
+    fn key(&self) -> (usize, usize) {
+        let worst = self
+            .count
+            .chunks(self.courts.max(1))
+            .map(|c| c.iter().max().unwrap_or(&0) - c.iter().min().unwrap_or(&0))
+            .max()
+            .unwrap_or(0);
+        (worst, self.count.iter().map(|c| c * c).sum())
+    }
+}
+
+/// Seed the placement by visiting rounds in `order`, then sweep to a local
+/// optimum. Returns each round's courts and the placement's quality key.
+fn rotate(
+    roster: Roster,
+    courts: &CourtPlan,
+    games: &[Vec<Game>],
+    fixed: &[Vec<Option<u16>>],
+    order: &[usize],
+) -> (Vec<Vec<u16>>, (usize, usize)) {
+    let most = courts.most() as usize;
+    let mut cnt = Counts { roster, courts: most, count: vec![0; roster.total_players() * most] };
+    let open: Vec<u16> = (0..games.len()).map(|r| courts.at(r)).collect();
+    let mut rounds: Vec<(Vec<Game>, Vec<u16>)> = vec![(Vec::new(), Vec::new()); games.len()];
+    for &r in order {
+        let court_of = place_round(&mut cnt, &games[r], &fixed[r], open[r]);
+        rounds[r] = (games[r].clone(), court_of);
+    }
+    // Each accepted change strictly lowers a non-negative integer, so this
+    // terminates; the cap only bounds pathological inputs.
+    for _ in 0..100 {
+        if !sweep(&mut cnt, &mut rounds, fixed, &open) {
+            break;
+        }
+    }
+    (rounds.into_iter().map(|(_, c)| c).collect(), cnt.key())
+}
+
+/// Reassign courts so each player rotates across all `courts` (a count, or
+/// the courts each round offers under a [`CourtPlan`]) as evenly as possible.
+/// Every round keeps exactly its games; only their courts change (games are
+/// listed in court order). Rounds with more games than courts are left as
+/// they are — the verifier reports those.
+///
+/// Sweeping settles in a local optimum that depends on the seeding order, so
+/// a few deterministic orders (strides through the rounds) are tried and the
+/// most even result kept.
+pub fn assign_courts(schedule: &Schedule, roster: Roster, courts: impl Into<CourtPlan>) -> Schedule {
+    assign_courts_pinned(schedule, roster, courts, &Pins::new())
+}
+
+/// [`assign_courts`], keeping every game `pins` fixes to a court (pinned with
+/// a court, or in a locked round) on that court. Pins to courts that do not
+/// exist are ignored here; the verifier reports those.
+pub fn assign_courts_pinned(
+    schedule: &Schedule,
+    roster: Roster,
+    courts: impl Into<CourtPlan>,
+    pins: &Pins,
+) -> Schedule {
+    let courts = &courts.into();
+    let fits = |i: usize, r: &Round| r.games.len() <= courts.at(i) as usize;
+    let games: Vec<Vec<Game>> = schedule
+        .rounds
+        .iter()
+        .enumerate()
+        .map(|(i, r)| if fits(i, r) { r.games.clone() } else { Vec::new() })
+        .collect();
+    let fixed: Vec<Vec<Option<u16>>> = schedule
+        .rounds
+        .iter()
+        .enumerate()
+        .map(|(i, r)| {
+            let fixed = if fits(i, r) { pins.fixed_courts(i, r) } else { Vec::new() };
+            fixed.into_iter().map(|c| c.filter(|&c| c < courts.at(i))).collect()
+        })
+        .collect();
+
+    let n = games.len();
+    let placed = (1..=n.min(8))
+        .filter(|&k| gcd(k, n) == 1)
+        .map(|stride| {
+            let order: Vec<usize> = (0..n).map(|i| i * stride % n).collect();
+            rotate(roster, courts, &games, &fixed, &order)
+        })
+        .min_by_key(|&(_, key)| key)
+        .map(|(placed, _)| placed)
+        .unwrap_or_default();
+
+    let rounds = schedule
+        .rounds
+        .iter()
+        .zip(games.into_iter().zip(placed))
+        .enumerate()
+        .map(|(i, (round, (games, court_of)))| {
+            if !fits(i, round) {
+                return round.clone();
+            }
+            let mut by_court: Vec<(u16, Game)> = court_of.into_iter().zip(games).collect();
+            by_court.sort_by_key(|&(c, _)| c);
+            let (courts, games) = by_court.into_iter().unzip();
+            Round::with_courts(games, courts)
+        })
+        .collect();
+    Schedule::new(rounds)
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
@@ -0,0 +1,155 @@
+//! Verifier rules: the checks [`verify`](crate::verify::verify) applies.
+//!
+//! The verifier first *measures* a schedule (games, rounds, ledgers,
+//! participation — the numbers every optimizer is judged by), then hands the
+//! [`Report`] to each [`Rule`] in turn. A rule inspects the schedule and
+//! appends violations (which make the schedule illegal) and/or soft scores.
+//!
+//! The structural checks and the two once-rules are the [`builtin`] set. A
+//! club adds its own rules — rest limits, pairing bans, court restrictions —
+//! by implementing [`Rule`] and passing them to
+//! [`verify_with`](crate::verify::verify_with), and drops a built-in by
+//! leaving it out of the list.
+
+use crate::model::{Game, Player, Schedule};
+use crate::verify::{in_range, on_roster, pair_slots, Report, Slot, Violation};
+use std::collections::BTreeMap;
+
+/// One check the verifier runs over a measured schedule.
+pub trait Rule {
+    /// Short identifier, used to tag violations and scores and to pick rules
+    /// per event.
+    fn name(&self) -> &'static str;
+
+    /// Inspect `schedule` and append violations and soft scores to `report`.
+    /// The report's measurements (games, ledgers, participation) are already
+    /// filled in.
+    fn apply(&self, schedule: &Schedule, report: &mut Report);
+}
+
+/// Every game well-formed, in range and on its own real court, nobody on two
+/// courts in one round; every side game on a court of its own, for players on
+/// a bye. Emits [`Violation::Malformed`], [`Violation::OutOfRange`],
+/// [`Violation::CourtOutOfRange`], [`Violation::CourtClash`],
+/// [`Violation::DoubleBooked`] and [`Violation::SideBooked`], in schedule
+/// order.
+pub struct Structure;
+
+impl Rule for Structure {
+    fn name(&self) -> &'static str {
+        "structure"
+    }
+
+    fn apply(&self, schedule: &Schedule, report: &mut Report) {
+        let roster = report.roster;
+        for (ri, round) in schedule.rounds.iter().enumerate() {
+            let mut seen: BTreeMap<Player, Vec<Slot>> = BTreeMap::new();
+            let mut on_court: BTreeMap<usize, Vec<Slot>> = BTreeMap::new();
+            for (gi, game) in round.games.iter().enumerate() {
+                let at = Slot::of(round, ri, gi);
+                if at.court >= report.court_plan.at(ri) as usize {
+                    report.violations.push(Violation::CourtOutOfRange { at });
+                }
+                on_court.entry(at.court).or_default().push(at);
+                if !game.is_well_formed() {
+                    report.violations.push(Violation::Malformed { at });
+                }
+                if !in_range(game, roster) {
+                    for who in game.players() {
+                        let bad = match who {
+                            Player::M(m) => m.0 >= roster.men,
+                            Player::W(w) => w.0 >= roster.women,
+                        };
+                        if bad {
+                            report.violations.push(Violation::OutOfRange { at, who });
+                        }
+                    }
+                    continue;
+                }
+                for who in game.players() {
+                    seen.entry(who).or_default().push(at);
+                }
+            }
+            // Side games: a spare court each, and only players on a bye.
+            // Each side player's side-game courts so far (none for a drill).
+            let mut sided: BTreeMap<Player, Vec<Slot>> = BTreeMap::new();
+            for side in &round.side {
+                let here = side.court().map(|c| Slot { round: ri, court: c as usize });
+                if let Some(at) = here {
+                    if at.court >= report.court_plan.at(ri) as usize {
+                        report.violations.push(Violation::CourtOutOfRange { at });
+                    }
+                    on_court.entry(at.court).or_default().push(at);
+                }
+                for who in side.players() {
+                    let games = seen.get(&who).map_or(&[][..], Vec::as_slice);
+                    let earlier = sided.get(&who);
+                    if !on_roster(who, roster) || !games.is_empty() || earlier.is_some() {
+                        let mut at: Vec<Slot> =
+                            games.iter().chain(earlier.into_iter().flatten()).copied().collect();
+                        at.extend(here);
+                        at.sort();
+                        at.dedup();
+                        report.violations.push(Violation::SideBooked { round: ri, who, at });
+                    }
+                    sided.entry(who).or_default().extend(here);
+                }
+            }
+            for at in on_court.into_values() {
+                if at.len() > 1 {
+                    report.violations.push(Violation::CourtClash { at });
+                }
+            }
+            for (who, mut at) in seen {
+                // A malformed game lists one player twice in the same slot;
+                // that is already reported as `Malformed`, not double booking.
+                at.dedup();
+                if at.len() > 1 {
+                    report.violations.push(Violation::DoubleBooked { who, at });
+                }
+            }
+        }
+    }
+}
+
+/// Each `(man, woman)` partnership used at most once. Emits
+/// [`Violation::RepeatedPartnership`], sorted by `(man, woman)`.
+pub struct PartnerOnce;
+
+impl Rule for PartnerOnce {
+    fn name(&self) -> &'static str {
+        "partner-once"
+    }
+
+    fn apply(&self, schedule: &Schedule, report: &mut Report) {
+        for ((man, woman), at) in pair_slots(schedule, report.roster, Game::partnerships) {
+            if at.len() > 1 {
+                report.violations.push(Violation::RepeatedPartnership { man, woman, at });
+            }
+        }
+    }
+}
+
+/// Each `(man, woman)` mixed opposition at most once. Emits
+/// [`Violation::RepeatedMixedOpp`], sorted by `(man, woman)`.
+pub struct MixedOnce;
+
+impl Rule for MixedOnce {
+    fn name(&self) -> &'static str {
+        "mixed-once"
+    }
+
+    fn apply(&self, schedule: &Schedule, report: &mut Report) {
+        for ((man, woman), at) in pair_slots(schedule, report.roster, Game::mixed_opps) {
+            if at.len() > 1 {
+                report.violations.push(Violation::RepeatedMixedOpp { man, woman, at });
+            }
+        }
+    }
+}
+
+/// The built-in rule set, in the order [`verify`](crate::verify::verify)
+/// applies it: [`Structure`], [`PartnerOnce`], [`MixedOnce`].
+pub fn builtin() -> Vec<Box<dyn Rule>> {
+    vec![Box::new(Structure), Box::new(PartnerOnce), Box::new(MixedOnce)]
+}
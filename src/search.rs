@@ -19,31 +19,41 @@
 //! lowest same-gender-cost game. Simulated annealing with reheating, best of
 //! several independent runs, drives repeats down while keeping rounds full.
 //!
+//! Every phase also honours a [`Budget`] (deadline and/or cancellation), so
+//! [`optimize_within`] can promise an answer in fixed time for any roster.
+//!
+//! [`optimize_from`] warm-starts the same annealing from a known schedule
+//! (last week's, say) instead of the constructors, starting cold so most of
+//! its rounds survive; [`optimize_pinned`] anneals around [`Pins`] the ruin
+//! step never touches.
+//!
+//! [`optimize_in_season`] builds one night of a [`Season`]: the ledger also
+//! counts what the night repeats from earlier nights, and the annealing and
+//! the final choice pay [`Objective::history`] for it.
+//!
+//! Every entry point takes its courts as a [`CourtPlan`] — a bare count, or
+//! courts round by round when some free up partway through the evening. The
+//! annealing itself packs against the plan's most courts (its rounds are only
+//! scaffolding); the final repack lays the games out round by round within
+//! what each round offers.
+//!
 //! The verifier remains the final oracle — [`optimize`] returns a `Schedule`
 //! that `verify` scores exactly like any other.
 
-use crate::construct::{hsolssom, reflection};
-use crate::model::{Game, Man, Player, Roster, Round, Schedule, Team, Woman};
-use crate::verify::{verify, Report};
+use crate::budget::Budget;
+use crate::construct::{hsolssom_within, reflection};
+use crate::model::{CourtPlan, Game, Player, Roster, Round, Schedule};
+use crate::objective::Objective;
+use crate::pack::pack_exact;
+use crate::parallel::{self, derive_seed};
+use crate::pick::Picker;
+use crate::pins::Pins;
+use crate::season::Season;
+use crate::verify::{verify, LiveReport, Report};
 use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
 use rand::{Rng, SeedableRng};
-use std::collections::{HashMap, HashSet};
-
-// Same-gender soft weights used *inside* the search: max multiplicity dominates
-// (avoid facing the same opponent 3× while others meet once), then total excess,
-// then a nudge toward balanced participation. Court utilization is handled at
-// candidate-selection time, not here (see `optimize`).
-const W_MAX: f64 = 1000.0;
-const W_EXCESS: f64 = 10.0;
-const W_SPREAD: f64 = 1.0;
-
-/// Emphasis presets: `round_weight` is how much each extra round costs at
-/// candidate selection. Higher ⇒ fuller courts at the cost of more same-gender
-/// repeats. This is the one knob that slides along the Pareto frontier.
-pub const EMPHASIS_VARIETY: f64 = 0.0; // fewest repeats, courts may sit idle
-pub const EMPHASIS_BALANCED: f64 = 40.0; // sensible middle (default)
-pub const EMPHASIS_COURTS: f64 = 100_000.0; // full courts above all else
+use std::time::Duration;
 
 /// Same-gender / participation stats for a schedule (no packing — rounds are
 /// judged later against the real repack, so the search itself stays consistent).
@@ -55,205 +65,94 @@ struct Stats {
     man_max: usize,
     woman_max: usize,
     part_spread: usize,
+    /// Warm-start rounds no longer in the schedule (0 for cold starts).
+    churned: usize,
+    /// Repeats and same-gender meetings from earlier nights of a season.
+    stale: usize,
+    stale_met: usize,
 }
 
 impl Stats {
-    fn soft(&self) -> f64 {
-        W_MAX * (self.man_max + self.woman_max) as f64
-            + W_EXCESS * (self.man_excess + self.woman_excess) as f64
-            + W_SPREAD * self.part_spread as f64
+    /// Read the stats off the live ledger — the verifier's own numbers.
+    fn of(live: &LiveReport) -> Self {
+        Stats {
+            games: live.games(),
+            man_excess: live.man_repeat_excess(),
+            woman_excess: live.woman_repeat_excess(),
+            man_max: live.man_max_meetings(),
+            woman_max: live.woman_max_meetings(),
+            part_spread: live.participation_spread(),
+            churned: 0,
+            stale: live.season_repeats(),
+            stale_met: live.season_meetings(),
+        }
+    }
+
+    /// Same-gender / participation cost under `obj`. Court utilization is
+    /// handled at candidate-selection time, not here (see [`optimize`]).
+    fn soft(&self, obj: &Objective) -> f64 {
+        obj.max_meetings * (self.man_max + self.woman_max) as f64
+            + obj.excess * (self.man_excess + self.woman_excess + self.stale_met) as f64
+            + obj.spread * self.part_spread as f64
+            + obj.churn * self.churned as f64
+            + obj.history * self.stale as f64
     }
 
     /// Lexicographic quality: more games first, then lower same-gender cost.
-    fn better_than(&self, other: &Stats) -> bool {
+    fn better_than(&self, other: &Stats, obj: &Objective) -> bool {
         if self.games != other.games {
             self.games > other.games
         } else {
-            self.soft() < other.soft()
-        }
-    }
-}
-
-/// Compute same-gender and participation stats for a schedule.
-fn stats_of(rounds: &[Vec<Game>], roster: Roster) -> Stats {
-    let mut man: HashMap<(u16, u16), usize> = HashMap::new();
-    let mut woman: HashMap<(u16, u16), usize> = HashMap::new();
-    let mut per_man = vec![0usize; roster.men as usize];
-    let mut per_woman = vec![0usize; roster.women as usize];
-    let mut games = 0usize;
-
-    for round in rounds {
-        for g in round {
-            games += 1;
-            let mp = g.man_pair();
-            *man.entry((mp.0 .0, mp.1 .0)).or_insert(0) += 1;
-            let wp = g.woman_pair();
-            *woman.entry((wp.0 .0, wp.1 .0)).or_insert(0) += 1;
-            per_man[g.a.man.0 as usize] += 1;
-            per_man[g.b.man.0 as usize] += 1;
-            per_woman[g.a.woman.0 as usize] += 1;
-            per_woman[g.b.woman.0 as usize] += 1;
+            self.soft(obj) < other.soft(obj)
         }
     }
-
-    let all = per_man.iter().chain(per_woman.iter());
-    let part_spread = all.clone().max().copied().unwrap_or(0) - all.min().copied().unwrap_or(0);
-
-    Stats {
-        games,
-        man_excess: man.values().map(|&c| c - 1).sum(),
-        woman_excess: woman.values().map(|&c| c - 1).sum(),
-        man_max: man.values().copied().max().unwrap_or(0),
-        woman_max: woman.values().copied().max().unwrap_or(0),
-        part_spread,
-    }
-}
-
-/// Live hard-constraint ledgers plus same-gender meeting counts. Pure
-/// bookkeeping — game storage lives in the round structure.
-struct Ledger {
-    partner: HashSet<(u16, u16)>,
-    mixed: HashSet<(u16, u16)>,
-    man_meet: HashMap<(u16, u16), usize>,
-    woman_meet: HashMap<(u16, u16), usize>,
-}
-
-impl Ledger {
-    fn new() -> Self {
-        Ledger {
-            partner: HashSet::new(),
-            mixed: HashSet::new(),
-            man_meet: HashMap::new(),
-            woman_meet: HashMap::new(),
-        }
-    }
-
-    fn man_key(a: Man, b: Man) -> (u16, u16) {
-        if a.0 <= b.0 { (a.0, b.0) } else { (b.0, a.0) }
-    }
-    fn woman_key(x: Woman, y: Woman) -> (u16, u16) {
-        if x.0 <= y.0 { (x.0, y.0) } else { (y.0, x.0) }
-    }
-
-    fn from_rounds(rounds: &[Vec<Game>]) -> Self {
-        let mut l = Ledger::new();
-        for round in rounds {
-            for g in round {
-                l.add(g);
-            }
-        }
-        l
-    }
-
-    fn add(&mut self, g: &Game) {
-        for (m, w) in g.partnerships() {
-            self.partner.insert((m.0, w.0));
-        }
-        for (m, w) in g.mixed_opps() {
-            self.mixed.insert((m.0, w.0));
-        }
-        *self.man_meet.entry(Self::man_key(g.a.man, g.b.man)).or_insert(0) += 1;
-        *self
-            .woman_meet
-            .entry(Self::woman_key(g.a.woman, g.b.woman))
-            .or_insert(0) += 1;
-    }
-
-    /// A legal game from four players, or `None` if neither orientation avoids
-    /// a repeated partnership or mixed opposition.
-    fn legal_game(&self, a: Man, b: Man, x: Woman, y: Woman) -> Option<Game> {
-        if !self.partner.contains(&(a.0, x.0))
-            && !self.partner.contains(&(b.0, y.0))
-            && !self.mixed.contains(&(a.0, y.0))
-            && !self.mixed.contains(&(b.0, x.0))
-        {
-            return Some(Game::new(Team::new(a, x), Team::new(b, y)));
-        }
-        if !self.partner.contains(&(a.0, y.0))
-            && !self.partner.contains(&(b.0, x.0))
-            && !self.mixed.contains(&(a.0, x.0))
-            && !self.mixed.contains(&(b.0, y.0))
-        {
-            return Some(Game::new(Team::new(a, y), Team::new(b, x)));
-        }
-        None
-    }
-
-    fn soft_cost(&self, a: Man, b: Man, x: Woman, y: Woman) -> usize {
-        self.man_meet.get(&Self::man_key(a, b)).copied().unwrap_or(0)
-            + self.woman_meet.get(&Self::woman_key(x, y)).copied().unwrap_or(0)
-    }
 }
 
 /// Build one round: fill up to `courts` player-disjoint games from players not
 /// yet used this round, always taking a minimum same-gender-cost legal game
 /// (ties broken uniformly). Returns the games; empty if none can be formed.
-fn build_round(led: &mut Ledger, roster: Roster, courts: u16, rng: &mut StdRng) -> Vec<Game> {
-    let mut used_m: HashSet<u16> = HashSet::new();
-    let mut used_w: HashSet<u16> = HashSet::new();
+fn build_round(led: &mut LiveReport, roster: Roster, courts: u16, rng: &mut StdRng) -> Vec<Game> {
     let mut round = Vec::new();
+    fill_round(led, roster, courts, &mut round, rng);
+    round
+}
 
-    for _ in 0..courts {
-        let mut men: Vec<Man> = roster.men_iter().filter(|m| !used_m.contains(&m.0)).collect();
-        let mut women: Vec<Woman> = roster
-            .women_iter()
-            .filter(|w| !used_w.contains(&w.0))
-            .collect();
-        // Shuffle so restarts explore different packings, not just one shape.
-        men.shuffle(rng);
-        women.shuffle(rng);
-
-        let mut best_cost = usize::MAX;
-        let mut best: Option<Game> = None;
-        let mut ties = 0u32;
-
-        for i in 0..men.len() {
-            for j in (i + 1)..men.len() {
-                let (a, b) = (men[i], men[j]);
-                for k in 0..women.len() {
-                    for l in (k + 1)..women.len() {
-                        let (x, y) = (women[k], women[l]);
-                        if let Some(g) = led.legal_game(a, b, x, y) {
-                            let c = led.soft_cost(a, b, x, y);
-                            if c < best_cost {
-                                best_cost = c;
-                                best = Some(g);
-                                ties = 1;
-                            } else if c == best_cost {
-                                ties += 1;
-                                if rng.random_range(0..ties) == 0 {
-                                    best = Some(g);
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-
-        match best {
+/// [`build_round`] for a round that already holds some games (recorded in
+/// `led`): add games around them until `courts` are in use or none fit.
+fn fill_round(
+    led: &mut LiveReport,
+    roster: Roster,
+    courts: u16,
+    round: &mut Vec<Game>,
+    rng: &mut StdRng,
+) {
+    let mut picker = Picker::new(led, roster, round);
+    while round.len() < courts as usize {
+        match picker.pick(led, rng) {
             Some(g) => {
-                led.add(&g);
-                used_m.insert(g.a.man.0);
-                used_m.insert(g.b.man.0);
-                used_w.insert(g.a.woman.0);
-                used_w.insert(g.b.woman.0);
+                led.add_game(&g);
+                picker.take(&g);
                 round.push(g);
             }
             None => break,
         }
     }
-
-    round
 }
 
 /// Append full rounds until no more games can be formed from the current
-/// ledger state.
-fn build_rounds(led: &mut Ledger, roster: Roster, courts: u16, rng: &mut StdRng) -> Vec<Vec<Game>> {
+/// ledger state, the `i`th new round filling `courts.at(i)` courts.
+fn build_rounds(
+    led: &mut LiveReport,
+    roster: Roster,
+    courts: &CourtPlan,
+    rng: &mut StdRng,
+) -> Vec<Vec<Game>> {
     let mut rounds = Vec::new();
     loop {
-        let round = build_round(led, roster, courts, rng);
+        led.new_round();
+        let round = build_round(led, roster, courts.at(rounds.len()), rng);
         if round.is_empty() {
+            led.remove_round(&round);
             break;
         }
         rounds.push(round);
@@ -263,17 +162,29 @@ fn build_rounds(led: &mut Ledger, roster: Roster, courts: u16, rng: &mut StdRng)
 
 /// Court-first construction: best of many full-round builds, minimizing the
 /// round count first (fullest courts) and same-gender second. Reliably finds
-/// the resolvable, fully-packed corner of the frontier.
-fn court_first(roster: Roster, courts: u16, restarts: u32, rng: &mut StdRng) -> Vec<Vec<Game>> {
+/// the resolvable, fully-packed corner of the frontier. Stops early, after at
+/// least one build, when `budget` expires. Each build starts from the empty
+/// ledger `night`.
+fn court_first(
+    night: &LiveReport,
+    courts: &CourtPlan,
+    restarts: u32,
+    obj: &Objective,
+    budget: &Budget,
+    rng: &mut StdRng,
+) -> Vec<Vec<Game>> {
     let mut best: Option<Vec<Vec<Game>>> = None;
     // Maximize games first, then minimize rounds (fullest courts), then
     // same-gender. Without the games term this degenerates to a tiny schedule.
     let mut best_key = (i64::MAX, usize::MAX, f64::MAX);
-    for _ in 0..restarts.max(1) {
-        let mut led = Ledger::new();
-        let rounds = build_rounds(&mut led, roster, courts, rng);
-        let st = stats_of(&rounds, roster);
-        let key = (-(st.games as i64), rounds.len(), st.soft());
+    for i in 0..restarts.max(1) {
+        if i > 0 && budget.expired() {
+            break;
+        }
+        let mut led = night.clone();
+        let rounds = build_rounds(&mut led, night.roster(), courts, rng);
+        let st = Stats::of(&led);
+        let key = (-(st.games as i64), rounds.len(), st.soft(obj));
         if key < best_key {
             best_key = key;
             best = Some(rounds);
@@ -282,119 +193,416 @@ fn court_first(roster: Roster, courts: u16, restarts: u32, rng: &mut StdRng) ->
     best.unwrap_or_default()
 }
 
+/// How often (in steps) an annealing run reports [`Progress`].
+const PROGRESS_EVERY: u32 = 256;
+
+/// A snapshot of a running optimization, reported by
+/// [`optimize_with_progress`] and [`Optimizer::progress`]. The metrics are
+/// those of the best schedule the current annealing start has found so far
+/// (before the final repack, so `rounds` can still shrink).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Progress {
+    /// Which annealing start this is (`0..starts`).
+    pub start: usize,
+    pub starts: usize,
+    /// Steps taken in this start, and how many it will take (`None` while a
+    /// time-budgeted run is still timing itself).
+    pub step: u32,
+    pub steps: Option<u32>,
+    pub games: usize,
+    pub rounds: usize,
+    pub man_excess: usize,
+    pub woman_excess: usize,
+    /// Current annealing temperature (high early, cooling toward the end of
+    /// each reheat cycle).
+    pub temperature: f64,
+}
+
 /// One ruin-and-recreate annealing run over round-structured schedules,
-/// minimizing same-gender oppositions. Returns the best game set found.
-fn anneal(roster: Roster, courts: u16, iters: u32, rng: &mut StdRng) -> Vec<Vec<Game>> {
-    let mut led = Ledger::new();
-    let mut cur = build_rounds(&mut led, roster, courts, rng);
-    let mut cur_stats = stats_of(&cur, roster);
-    let mut best = cur.clone();
-    let mut best_stats = cur_stats;
-
-    let (t0, t_end) = (12.0_f64, 0.05_f64);
-    let reheat_span = (iters / 4).max(2_000);
-    let mut cycle_pos = 0u32;
-    let mut since_improve = 0u32;
-
-    for _ in 0..iters {
-        let frac = cycle_pos as f64 / reheat_span as f64;
-        let t = t0 * (t_end / t0).powf(frac.min(1.0));
-
-        // Ruin: drop 1–2 whole rounds, opening ledger slack.
-        let mut trial = cur.clone();
-        if !trial.is_empty() {
-            let k = rng.random_range(1..=2usize).min(trial.len());
-            for _ in 0..k {
-                let idx = rng.random_range(0..trial.len());
-                trial.swap_remove(idx);
+/// minimizing same-gender oppositions, as resumable state: [`Anneal::run`]
+/// may be called repeatedly with fresh budgets until it reports done.
+///
+/// Runs `iters` steps, or — when `iters` is `None` — as many as fit in the
+/// budget given to [`Anneal::new`]: the first steps are timed and the cooling
+/// schedule is sized to the remaining time. Either way it stops early if the
+/// budget expires.
+///
+/// A warm start begins from the given rounds (topped up with new ones if the
+/// ledgers have room) at [`T_WARM`] instead of [`T_START`]. A pinned start
+/// keeps the pinned rounds first, in order: ruin only strips their unpinned
+/// games (never a locked round's) and recreate refills them.
+struct Anneal {
+    roster: Roster,
+    /// The courts each pinned round offers.
+    plan: CourtPlan,
+    /// Scaffolding for the free rounds: the plan's most courts, every round.
+    courts: CourtPlan,
+    obj: Objective,
+    rng: StdRng,
+    /// The warm start's rounds (empty for a cold start).
+    warm: Vec<Vec<Game>>,
+    /// The pinned games of each of the first `pinned.len()` rounds.
+    pinned: Vec<Vec<Game>>,
+    locked: Vec<bool>,
+    led: LiveReport,
+    cur: Vec<Vec<Game>>,
+    cur_stats: Stats,
+    best: Vec<Vec<Game>>,
+    best_led: LiveReport,
+    best_stats: Stats,
+    iters: u32,
+    calibrating: bool,
+    started: Option<Duration>,
+    reheat_span: u32,
+    cycle_pos: u32,
+    since_improve: u32,
+    step: u32,
+    t_start: f64,
+    temperature: f64,
+}
+
+/// What one ruin-and-recreate step changed in [`Anneal`]'s schedule and
+/// ledger, so a rejected step is undone in place rather than built on a copy.
+struct Undo {
+    /// Free rounds taken out with `swap_remove`, in order: index and games.
+    dropped: Vec<(usize, Vec<Game>)>,
+    /// Pinned rounds stripped to their pins: index and games before.
+    stripped: Vec<(usize, Vec<Game>)>,
+    /// Rounds left before the rebuild appended new ones.
+    kept: usize,
+}
+
+impl Undo {
+    /// Put `cur` and `led` back as they were before the step.
+    fn apply(self, cur: &mut Vec<Vec<Game>>, led: &mut LiveReport, pinned: &[Vec<Game>]) {
+        for round in cur.drain(self.kept..).rev() {
+            led.remove_round(&round);
+        }
+        for (idx, before) in self.stripped {
+            let pins = &pinned[idx];
+            for g in cur[idx].iter().filter(|g| !pins.contains(g)) {
+                led.remove_game(g);
+            }
+            for g in before.iter().filter(|g| !pins.contains(g)) {
+                led.add_game(g);
             }
+            cur[idx] = before;
         }
-        // Recreate: rebuild full rounds from the reduced state.
-        let mut trial_led = Ledger::from_rounds(&trial);
-        trial.extend(build_rounds(&mut trial_led, roster, courts, rng));
-        let ts = stats_of(&trial, roster);
+        for (idx, round) in self.dropped.into_iter().rev() {
+            led.add_round(&round);
+            cur.push(round);
+            let last = cur.len() - 1;
+            cur.swap(idx, last);
+        }
+    }
+}
 
-        let accept = if ts.games != cur_stats.games {
-            ts.games > cur_stats.games
-        } else {
-            let delta = ts.soft() - cur_stats.soft();
-            delta <= 0.0 || rng.random::<f64>() < (-delta / t).exp()
-        };
+const T_START: f64 = 12.0;
+const T_END: f64 = 0.05;
+
+/// The temperature `pos` steps into a reheat cycle of `span` steps: geometric
+/// cooling from `t_start` to [`T_END`], then holding there. Shared with the
+/// Part 2 annealer in [`target`](crate::target).
+pub(crate) fn cooled(t_start: f64, pos: u32, span: u32) -> f64 {
+    let frac = pos as f64 / span as f64;
+    t_start * (T_END / t_start).powf(frac.min(1.0))
+}
+
+/// Where annealing starts: from nothing (the constructors supply the other
+/// candidates), from a known schedule's rounds, or around pinned content.
+#[derive(Clone, Copy)]
+enum Start<'a> {
+    Cold,
+    Warm(&'a [Vec<Game>]),
+    Pinned(&'a Pins),
+}
 
-        if accept {
-            cur = trial;
-            cur_stats = ts;
+/// Starting temperature for a warm start: low enough that the rounds given
+/// mostly survive, high enough to escape their local optimum.
+const T_WARM: f64 = 2.0;
+
+impl Anneal {
+    /// A run from the empty ledger `night`.
+    fn new(
+        night: &LiveReport,
+        plan: &CourtPlan,
+        obj: Objective,
+        iters: Option<u32>,
+        start: Start,
+        budget: &Budget,
+        mut rng: StdRng,
+    ) -> Self {
+        let roster = night.roster();
+        let courts = CourtPlan::uniform(plan.most());
+        let mut led = night.clone();
+        let (warm, pinned, locked) = match start {
+            Start::Cold => (Vec::new(), Vec::new(), Vec::new()),
+            Start::Warm(rounds) => (rounds.to_vec(), Vec::new(), Vec::new()),
+            Start::Pinned(pins) => (
+                Vec::new(),
+                (0..pins.span()).map(|i| pins.pinned_round(i).games).collect(),
+                (0..pins.span()).map(|i| pins.is_locked(i)).collect(),
+            ),
+        };
+        let mut cur = if warm.is_empty() { pinned.clone() } else { warm.clone() };
+        for round in &cur {
+            led.add_round(round);
         }
+        for (i, (round, &locked)) in cur.iter_mut().zip(&locked).enumerate() {
+            if !locked {
+                fill_round(&mut led, roster, plan.at(i), round, &mut rng);
+            }
+        }
+        cur.extend(build_rounds(&mut led, roster, &courts, &mut rng));
+        let t_start = if warm.is_empty() { T_START } else { T_WARM };
+        let cur_stats = Stats::of(&led);
+        let iters = iters.unwrap_or(u32::MAX);
+        Anneal {
+            roster,
+            plan: plan.clone(),
+            courts,
+            obj,
+            rng,
+            warm,
+            pinned,
+            locked,
+            best: cur.clone(),
+            best_led: led.clone(),
+            best_stats: cur_stats,
+            led,
+            cur,
+            cur_stats,
+            iters,
+            calibrating: iters == u32::MAX && budget.remaining().is_some(),
+            started: budget.remaining(),
+            reheat_span: if iters == u32::MAX { 10_000 } else { (iters / 4).max(2_000) },
+            cycle_pos: 0,
+            since_improve: 0,
+            step: 0,
+            t_start,
+            temperature: t_start,
+        }
+    }
 
-        cycle_pos += 1;
-        if cur_stats.better_than(&best_stats) {
-            best = cur.clone();
-            best_stats = cur_stats;
-            since_improve = 0;
-        } else {
-            since_improve += 1;
+    /// Step until done or `budget` expires, reporting through `on_progress`
+    /// every [`PROGRESS_EVERY`] steps. Returns whether the run is done.
+    fn run(&mut self, budget: &Budget, on_progress: &mut dyn FnMut(&Self)) -> bool {
+        let roster = self.roster;
+        while self.step < self.iters {
+            if budget.expired() {
+                return false;
+            }
+            // Time-budgeted: once enough steps are timed to beat clock
+            // resolution, fix the step count to what fits before the deadline.
+            if self.calibrating {
+                if let (Some(t0_left), Some(left)) = (self.started, budget.remaining()) {
+                    let spent = t0_left.saturating_sub(left);
+                    if self.step > 0 && spent.as_millis() >= 20 {
+                        let per_step = spent.as_secs_f64() / self.step as f64;
+                        let more = (left.as_secs_f64() / per_step).min((u32::MAX / 2) as f64);
+                        self.iters = self.step + more as u32;
+                        self.reheat_span = (self.iters / 4).max(2_000);
+                        self.calibrating = false;
+                    }
+                }
+            }
+            self.step += 1;
+
+            let t = cooled(self.t_start, self.cycle_pos, self.reheat_span);
+            self.temperature = t;
+
+            // Ruin: drop 1–2 whole rounds, opening ledger slack. A pinned
+            // round stays, losing only its unpinned games. The step edits
+            // `cur` and `led` in place and is undone if rejected.
+            let rng = &mut self.rng;
+            let mut undo = Undo { dropped: Vec::new(), stripped: Vec::new(), kept: 0 };
+            if !self.cur.is_empty() {
+                let k = rng.random_range(1..=2usize).min(self.cur.len());
+                for _ in 0..k {
+                    let idx = rng.random_range(0..self.cur.len());
+                    if idx >= self.pinned.len() {
+                        let round = self.cur.swap_remove(idx);
+                        self.led.remove_round(&round);
+                        undo.dropped.push((idx, round));
+                    } else if !self.locked[idx] {
+                        let pins = &self.pinned[idx];
+                        for g in self.cur[idx].iter().filter(|g| !pins.contains(g)) {
+                            self.led.remove_game(g);
+                        }
+                        if undo.stripped.iter().all(|(i, _)| *i != idx) {
+                            undo.stripped.push((idx, self.cur[idx].clone()));
+                        }
+                        self.cur[idx].retain(|g| pins.contains(g));
+                    }
+                }
+            }
+            // Recreate: refill stripped pinned rounds, then rebuild full
+            // rounds from the reduced state.
+            for &(idx, _) in &undo.stripped {
+                fill_round(&mut self.led, roster, self.plan.at(idx), &mut self.cur[idx], rng);
+            }
+            undo.kept = self.cur.len();
+            self.cur.extend(build_rounds(&mut self.led, roster, &self.courts, rng));
+            let mut ts = Stats::of(&self.led);
+            ts.churned = churned(&self.warm, &self.cur);
+
+            let accept = if ts.games != self.cur_stats.games {
+                ts.games > self.cur_stats.games
+            } else {
+                let delta = ts.soft(&self.obj) - self.cur_stats.soft(&self.obj);
+                delta <= 0.0 || rng.random::<f64>() < (-delta / t).exp()
+            };
+
+            if accept {
+                self.cur_stats = ts;
+            } else {
+                undo.apply(&mut self.cur, &mut self.led, &self.pinned);
+            }
+
+            self.cycle_pos += 1;
+            if self.cur_stats.better_than(&self.best_stats, &self.obj) {
+                self.best = self.cur.clone();
+                self.best_led = self.led.clone();
+                self.best_stats = self.cur_stats;
+                self.since_improve = 0;
+            } else {
+                self.since_improve += 1;
+            }
+
+            if self.since_improve >= self.reheat_span {
+                self.cur = self.best.clone();
+                self.led = self.best_led.clone();
+                self.cur_stats = self.best_stats;
+                self.cycle_pos = 0;
+                self.since_improve = 0;
+            }
+
+            if self.step.is_multiple_of(PROGRESS_EVERY) {
+                on_progress(self);
+            }
         }
+        true
+    }
 
-        if since_improve >= reheat_span {
-            cur = best.clone();
-            cur_stats = best_stats;
-            cycle_pos = 0;
-            since_improve = 0;
+    fn progress(&self, start: usize, starts: usize) -> Progress {
+        Progress {
+            start,
+            starts,
+            step: self.step,
+            steps: (!self.calibrating && self.iters != u32::MAX).then_some(self.iters),
+            games: self.best_stats.games,
+            rounds: self.best.len(),
+            man_excess: self.best_stats.man_excess,
+            woman_excess: self.best_stats.woman_excess,
+            temperature: self.temperature,
         }
     }
 
-    best
+    /// The best game set found so far.
+    fn best_games(&self) -> Vec<Game> {
+        self.best.iter().flatten().copied().collect()
+    }
+}
+
+/// Rounds of `warm` missing from `rounds`.
+fn churned(warm: &[Vec<Game>], rounds: &[Vec<Game>]) -> usize {
+    warm.iter().filter(|w| !rounds.contains(w)).count()
 }
 
-/// Repack a game set into as few rounds as possible (each round ≤ `courts`
-/// player-disjoint games), most-constrained-player first, best of many
+/// Randomized tie-breaks per [`repack`].
+const REPACK_TRIES: u32 = 400;
+
+/// Repack a game set into as few rounds as possible (round `i` holding at most
+/// `courts.at(i)` player-disjoint games), most-constrained-player first, best of many
 /// randomized tie-breaks. Tightens the round-structured result without
-/// changing the game set (so same-gender stats are preserved).
-fn repack(games: &[Game], courts: u16, rng: &mut StdRng) -> Schedule {
-    let c = courts as usize;
-    let tries = 400;
+/// changing the game set (so same-gender stats are preserved). Stops early,
+/// after at least one try, when `budget` expires.
+pub(crate) fn repack(
+    games: &[Game],
+    courts: &CourtPlan,
+    tries: u32,
+    budget: &Budget,
+    rng: &mut StdRng,
+) -> Schedule {
+    let direct = repack_to(games, |i| courts.at(i), tries, budget, rng);
+    if courts.is_uniform() {
+        return direct;
+    }
+    // Packing on the most courts and splitting rounds across the leaner ones
+    // keeps whole parallel classes together, which round-by-round packing
+    // often misses.
+    let most = courts.most();
+    let split = split_rounds(repack_to(games, |_| most, tries, budget, rng), courts);
+    if split.num_rounds() < direct.num_rounds() { split } else { direct }
+}
+
+/// `sched`'s rounds in order, each split over as many rounds as `courts`
+/// needs to seat it.
+fn split_rounds(sched: Schedule, courts: &CourtPlan) -> Schedule {
+    let mut rounds: Vec<Round> = Vec::new();
+    for round in sched.rounds {
+        let mut games = round.games;
+        while !games.is_empty() {
+            let rest = games.split_off(games.len().min(courts.at(rounds.len()) as usize));
+            rounds.push(Round::new(games));
+            games = rest;
+        }
+    }
+    Schedule::new(rounds)
+}
+
+/// [`repack`] with round `i` holding at most `cap(i)` games.
+fn repack_to(
+    games: &[Game],
+    cap: impl Fn(usize) -> u16,
+    tries: u32,
+    budget: &Budget,
+    rng: &mut StdRng,
+) -> Schedule {
+    // Dense player slots: men by index, then women after the largest man.
+    let men = games.iter().map(|g| g.a.man.0.max(g.b.man.0) as usize + 1).max().unwrap_or(0);
+    let women = games.iter().map(|g| g.a.woman.0.max(g.b.woman.0) as usize + 1).max().unwrap_or(0);
+    let players = men + women;
+    let slot = |p: Player| match p {
+        Player::M(m) => m.0 as usize,
+        Player::W(w) => men + w.0 as usize,
+    };
+    let seats: Vec<[usize; 4]> = games.iter().map(|g| g.players().map(slot)).collect();
     let mut best: Option<Schedule> = None;
     let mut best_key = (usize::MAX, i64::MIN);
 
-    for _ in 0..tries {
-        let mut remaining = games.to_vec();
+    for t in 0..tries {
+        if t > 0 && budget.expired() {
+            break;
+        }
+        let mut remaining: Vec<usize> = (0..games.len()).collect();
         remaining.shuffle(rng);
 
         let mut rounds: Vec<Vec<Game>> = Vec::new();
         while !remaining.is_empty() {
-            let mut deg: HashMap<Player, usize> = HashMap::new();
-            for g in &remaining {
-                for p in g.players() {
-                    *deg.entry(p).or_insert(0) += 1;
+            let mut deg = vec![0usize; players];
+            for &i in &remaining {
+                for p in seats[i] {
+                    deg[p] += 1;
                 }
             }
+            // A game's score only changes between rounds, so taking the best
+            // free game court by court is one pass in score order.
+            remaining.sort_by_key(|&i| std::cmp::Reverse(seats[i].iter().map(|&p| deg[p]).sum::<usize>()));
 
+            let c = cap(rounds.len()) as usize;
             let mut round: Vec<Game> = Vec::new();
-            let mut used: HashSet<Player> = HashSet::new();
-            while round.len() < c {
-                let mut pick: Option<usize> = None;
-                let mut best_score = -1i64;
-                for (i, g) in remaining.iter().enumerate() {
-                    if g.players().iter().any(|p| used.contains(p)) {
-                        continue;
-                    }
-                    let score: i64 = g.players().iter().map(|p| deg[p] as i64).sum();
-                    if score > best_score {
-                        best_score = score;
-                        pick = Some(i);
-                    }
+            let mut used = vec![false; players];
+            remaining.retain(|&i| {
+                if round.len() == c || seats[i].iter().any(|&p| used[p]) {
+                    return true;
                 }
-                match pick {
-                    Some(i) => {
-                        let g = remaining.swap_remove(i);
-                        for p in g.players() {
-                            used.insert(p);
-                        }
-                        round.push(g);
-                    }
-                    None => break,
+                for p in seats[i] {
+                    used[p] = true;
                 }
-            }
+                round.push(games[i]);
+                false
+            });
             rounds.push(round);
         }
 
@@ -413,90 +621,590 @@ fn repack(games: &[Game], courts: u16, rng: &mut StdRng) -> Schedule {
 /// (best of N kills seed-to-seed variance), then a final repack of the winner.
 /// `ls_iters` is the total step budget, split across the runs.
 ///
-/// `round_weight` slides along the court-fullness ⇄ same-gender frontier — see
-/// [`EMPHASIS_VARIETY`], [`EMPHASIS_BALANCED`], [`EMPHASIS_COURTS`].
+/// `objective` weighs the terms; its [`rounds`](Objective::rounds) weight
+/// slides along the court-fullness ⇄ same-gender frontier — see the presets
+/// [`Objective::courts`], [`Objective::balanced`], [`Objective::variety`].
+///
+/// `courts` is a court count or a per-round [`CourtPlan`].
 pub fn optimize(
     roster: Roster,
-    courts: u16,
+    courts: impl Into<CourtPlan>,
     ls_iters: u32,
-    round_weight: f64,
+    objective: &Objective,
+    seed: u64,
+) -> Schedule {
+    optimize_within(roster, courts, Some(ls_iters), &Budget::unlimited(), objective, seed)
+}
+
+/// [`optimize`] under a [`Budget`]: a wall-clock deadline and/or cancellation
+/// token. With `ls_iters = None` the search uses the whole budget; with a
+/// step count it stops at whichever comes first. Either way it returns the
+/// best schedule found so far — a valid one even if the budget is already
+/// spent.
+pub fn optimize_within(
+    roster: Roster,
+    courts: impl Into<CourtPlan>,
+    ls_iters: Option<u32>,
+    budget: &Budget,
+    objective: &Objective,
     seed: u64,
 ) -> Schedule {
+    optimize_with_progress(roster, courts, ls_iters, budget, objective, seed, &|_| {})
+}
+
+/// [`optimize_within`], calling `on_progress` as the annealing starts run.
+/// Starts may run on several threads at once, so reports from different
+/// starts interleave; [`Progress::start`] tells them apart.
+pub fn optimize_with_progress(
+    roster: Roster,
+    courts: impl Into<CourtPlan>,
+    ls_iters: Option<u32>,
+    budget: &Budget,
+    objective: &Objective,
+    seed: u64,
+    on_progress: &(dyn Fn(&Progress) + Sync),
+) -> Schedule {
+    let courts = &courts.into();
+    let night = LiveReport::new(roster, courts);
+    match candidates(&night, courts, ls_iters, Start::Cold, budget, objective, seed, on_progress) {
+        Seeded::Optimal(sched) => sched,
+        Seeded::Candidates(c) => {
+            tighten(select(c, &night, courts, objective), roster, courts, budget)
+        }
+    }
+}
+
+/// [`optimize_with_progress`] for the next night of `season`, on its roster:
+/// besides the night's own terms, the search pays [`Objective::history`] for
+/// each earlier-night partnership and mixed opposition the night repeats, and
+/// counts its pairs' earlier same-gender meetings as excess meetings. A
+/// provably optimal construction is only one candidate among the rest here,
+/// as it knows nothing of the season. [`Season::fit`] can relabel the result
+/// afterwards.
+pub fn optimize_in_season(
+    season: &Season,
+    courts: impl Into<CourtPlan>,
+    ls_iters: Option<u32>,
+    budget: &Budget,
+    objective: &Objective,
+    seed: u64,
+    on_progress: &(dyn Fn(&Progress) + Sync),
+) -> Schedule {
+    let courts = &courts.into();
+    let night = LiveReport::in_season(season, courts);
+    match candidates(&night, courts, ls_iters, Start::Cold, budget, objective, seed, on_progress) {
+        Seeded::Optimal(sched) => sched,
+        Seeded::Candidates(c) => {
+            let best = select(c, &night, courts, objective);
+            tighten(best, season.roster(), courts, budget)
+        }
+    }
+}
+
+/// The whole courts ⇄ variety trade-off instead of one emphasis pick: every
+/// candidate [`optimize_within`] would choose from that no other beats, each
+/// with its [`Report`]. See [`pareto`] for the order and the dominance rule.
+/// `objective`'s round weight plays no part; its other weights still steer
+/// the search.
+pub fn optimize_frontier(
+    roster: Roster,
+    courts: impl Into<CourtPlan>,
+    ls_iters: Option<u32>,
+    budget: &Budget,
+    objective: &Objective,
+    seed: u64,
+) -> Vec<(Schedule, Report)> {
+    let courts = &courts.into();
+    let night = LiveReport::new(roster, courts);
+    match candidates(&night, courts, ls_iters, Start::Cold, budget, objective, seed, &|_| {}) {
+        Seeded::Optimal(sched) => pareto(vec![sched], roster, courts),
+        Seeded::Candidates(c) => pareto(c, roster, courts),
+    }
+}
+
+/// [`optimize_within`] warm-started from `initial` — last week's schedule, or
+/// one built for fewer courts — rather than from the constructors. Every start
+/// anneals from `initial`'s rounds at a low temperature, paying
+/// [`Objective::churn`] for each of them it tears up; surviving rounds keep
+/// their places and only the rebuilt games are repacked. `initial` itself is
+/// a candidate too, so it is only replaced by something better.
+///
+/// `initial` must pass [`verify`] for this roster and court count with no
+/// violations; otherwise the first violation comes back as the error.
+pub fn optimize_from(
+    initial: &Schedule,
+    roster: Roster,
+    courts: impl Into<CourtPlan>,
+    ls_iters: Option<u32>,
+    budget: &Budget,
+    objective: &Objective,
+    seed: u64,
+) -> Result<Schedule, String> {
+    let courts = &courts.into();
+    let warm = warm_rounds(initial, roster, courts, true)?;
+    let start = Start::Warm(&warm);
+    let night = LiveReport::new(roster, courts);
+    match candidates(&night, courts, ls_iters, start, budget, objective, seed, &|_| {}) {
+        Seeded::Optimal(sched) => Ok(sched),
+        Seeded::Candidates(c) => Ok(select(c, &night, courts, objective)),
+    }
+}
+
+/// [`optimize_within`] around `pins`: pinned games stay in their rounds (and
+/// on their courts), locked rounds stay exactly as given, and everything else
+/// is optimized around them. The pinned rounds come first, in order; the
+/// constructors are skipped, as they cannot honour pins. Add `pins` to
+/// [`verify_with`](crate::verify::verify_with) to confirm the result.
+///
+/// Errors if the pins alone already break a rule — two pinned games sharing a
+/// player or court in one round, a repeated partnership among them, more
+/// games than courts — naming the first such violation.
+pub fn optimize_pinned(
+    roster: Roster,
+    courts: impl Into<CourtPlan>,
+    ls_iters: Option<u32>,
+    budget: &Budget,
+    objective: &Objective,
+    pins: &Pins,
+    seed: u64,
+) -> Result<Schedule, String> {
+    let courts = &courts.into();
+    let skeleton = verify(&pins.skeleton(), roster, courts);
+    if let Some(v) = skeleton.violations.first() {
+        return Err(format!("pins cannot be honoured: {v}"));
+    }
+    let start = Start::Pinned(pins);
+    let night = LiveReport::new(roster, courts);
+    match candidates(&night, courts, ls_iters, start, budget, objective, seed, &|_| {}) {
+        Seeded::Optimal(sched) => Ok(sched),
+        Seeded::Candidates(c) => Ok(select(c, &night, courts, objective)),
+    }
+}
+
+/// The games of `initial` round by round, once [`verify`] accepts it: with no
+/// violations at all when `legal` (the Part 1 bar), otherwise with no
+/// structural ones (Part 2, where repeats are soft).
+pub(crate) fn warm_rounds(
+    initial: &Schedule,
+    roster: Roster,
+    courts: &CourtPlan,
+    legal: bool,
+) -> Result<Vec<Vec<Game>>, String> {
+    let report = verify(initial, roster, courts);
+    if let Some(v) = report.violations.iter().find(|v| legal || v.is_structural()) {
+        return Err(format!("starting schedule rejected: {v}"));
+    }
+    Ok(initial
+        .rounds
+        .iter()
+        .filter(|r| !r.games.is_empty())
+        .map(|r| r.games.clone())
+        .collect())
+}
+
+/// The non-dominated subset of `candidates`. Only schedules with the most games
+/// count (Part 1 maximizes games first); among them one dominates another when
+/// it is no worse on rounds, same-gender excess and participation spread and
+/// better on at least one. Options with identical metrics collapse to the
+/// first. Sorted by rounds (fullest courts first), so the list reads from the
+/// courts end of the frontier to the variety end.
+pub fn pareto(
+    candidates: Vec<Schedule>,
+    roster: Roster,
+    courts: impl Into<CourtPlan>,
+) -> Vec<(Schedule, Report)> {
+    let courts = &courts.into();
+    let scored: Vec<(Schedule, Report)> = candidates
+        .into_iter()
+        .map(|s| {
+            let r = verify(&s, roster, courts);
+            (s, r)
+        })
+        .collect();
+    let most = scored.iter().map(|(_, r)| r.games).max().unwrap_or(0);
+    let scored: Vec<(Schedule, Report)> = scored.into_iter().filter(|(_, r)| r.games == most).collect();
+    let key = |r: &Report| {
+        (r.rounds, r.man_repeat_excess + r.woman_repeat_excess, r.participation_spread())
+    };
+    let dominates = |a: (usize, usize, usize), b: (usize, usize, usize)| {
+        a.0 <= b.0 && a.1 <= b.1 && a.2 <= b.2 && a != b
+    };
+    let keys: Vec<_> = scored.iter().map(|(_, r)| key(r)).collect();
+    let mut front: Vec<(Schedule, Report)> = Vec::new();
+    for (i, (s, r)) in scored.into_iter().enumerate() {
+        let k = keys[i];
+        let beaten = keys.iter().any(|&o| dominates(o, k));
+        if !beaten && !front.iter().any(|(_, f)| key(f) == k) {
+            front.push((s, r));
+        }
+    }
+    front.sort_by_key(|(_, r)| key(r));
+    front
+}
+
+/// Every candidate the pipeline produces: the constructions (or the warm
+/// start, or nothing when pinned), then one per annealing start, each built
+/// from the empty ledger `night`.
+#[allow(clippy::too_many_arguments)]
+fn candidates(
+    night: &LiveReport,
+    courts: &CourtPlan,
+    ls_iters: Option<u32>,
+    start: Start,
+    budget: &Budget,
+    obj: &Objective,
+    seed: u64,
+    on_progress: &(dyn Fn(&Progress) + Sync),
+) -> Seeded {
+    let mut candidates = match start {
+        Start::Cold => match seed_candidates(night, courts, budget, obj, seed) {
+            Seeded::Optimal(sched) => return Seeded::Optimal(sched),
+            Seeded::Candidates(c) => c,
+        },
+        Start::Warm(rounds) => {
+            vec![Schedule::new(rounds.iter().cloned().map(Round::new).collect())]
+        }
+        Start::Pinned(_) => Vec::new(),
+    };
+
+    // Variety candidates: ruin-and-recreate (low same-gender corner), which
+    // have no round structure of their own, so repack them tightly. Each start
+    // runs on its own seed derived from `seed`, so the starts can run in
+    // parallel and still reproduce exactly.
+    let per = ls_iters.map(|n| (n / STARTS as u32).max(1));
+    let workers = parallel::workers(STARTS);
+    candidates.extend(parallel::run_indexed(STARTS, |i| {
+        // Under a time budget a start gets one worker's share of the starts
+        // not yet begun.
+        let job = budget.portion((workers as f64 / (STARTS - i) as f64).min(1.0));
+        let rng = StdRng::seed_from_u64(derive_seed(seed, i));
+        let anneal_budget = job.portion(0.9);
+        let mut run = Anneal::new(night, courts, *obj, per, start, &anneal_budget, rng);
+        run.run(&anneal_budget, &mut |a| on_progress(&a.progress(i, STARTS)));
+        on_progress(&run.progress(i, STARTS));
+        match start {
+            Start::Cold => repack(&run.best_games(), courts, REPACK_TRIES, &job, &mut run.rng),
+            Start::Warm(warm) => {
+                // Keep the warm start's layout: rounds that survived stay
+                // intact and in their places; only rebuilt games are repacked.
+                // Should a rebuilt round land on fewer courts than it needs,
+                // the whole game set is repacked instead.
+                let all = run.best_games();
+                let (mut kept, fresh): (Vec<_>, Vec<_>) =
+                    std::mem::take(&mut run.best).into_iter().partition(|r| warm.contains(r));
+                let fresh: Vec<Game> = fresh.into_iter().flatten().collect();
+                let packed = repack(&fresh, courts, REPACK_TRIES, &job, &mut run.rng);
+                kept.extend(packed.rounds.into_iter().map(|r| r.games));
+                let rounds = align(kept, warm);
+                if rounds.iter().enumerate().any(|(i, r)| r.len() > courts.at(i) as usize) {
+                    return repack(&all, courts, REPACK_TRIES, &job, &mut run.rng);
+                }
+                Schedule::new(rounds.into_iter().map(Round::new).collect())
+            }
+            Start::Pinned(pins) => {
+                // The pinned rounds stay put, on courts that honour the pins;
+                // the free rounds after them are repacked.
+                let mut slots = std::mem::take(&mut run.best);
+                let free: Vec<Game> = slots.split_off(pins.span()).into_iter().flatten().collect();
+                let free_courts = courts.after(pins.span());
+                let packed = repack(&free, &free_courts, REPACK_TRIES, &job, &mut run.rng);
+                let mut rounds: Vec<Round> =
+                    slots.into_iter().enumerate().map(|(i, games)| pins.place(i, games)).collect();
+                rounds.extend(packed.rounds);
+                Schedule::new(rounds)
+            }
+        }
+    }));
+    Seeded::Candidates(candidates)
+}
+
+/// `rounds` reordered so every round also in `warm` sits at its index there;
+/// the rest fill the gaps in their current order.
+fn align(mut rounds: Vec<Vec<Game>>, warm: &[Vec<Game>]) -> Vec<Vec<Game>> {
+    let mut slots: Vec<Option<Vec<Game>>> = vec![None; rounds.len()];
+    for (slot, w) in slots.iter_mut().zip(warm) {
+        if let Some(j) = rounds.iter().position(|r| r == w) {
+            *slot = Some(rounds.remove(j));
+        }
+    }
+    let mut rest = rounds.into_iter();
+    slots.into_iter().map(|s| s.or_else(|| rest.next()).expect("one round per slot")).collect()
+}
+
+/// Independent annealing starts per optimization.
+const STARTS: usize = 6;
+
+/// Outcome of the construction phase.
+enum Seeded {
+    /// A provably optimal construction — nothing can beat it.
+    Optimal(Schedule),
+    /// Constructed candidates for the annealing starts to compete with.
+    Candidates(Vec<Schedule>),
+}
+
+/// The construction phase shared by every entry point: the algebraic
+/// constructors and the court-first builder, drawing on `seed` directly.
+fn seed_candidates(
+    night: &LiveReport,
+    courts: &CourtPlan,
+    budget: &Budget,
+    obj: &Objective,
+    seed: u64,
+) -> Seeded {
     let mut rng = StdRng::seed_from_u64(seed);
+    let roster = night.roster();
     let n = roster.men as usize;
-    let balanced_even = roster.women as usize == n && n >= 2 && n % 2 == 0;
+    let balanced_even = roster.women as usize == n && n >= 2 && n.is_multiple_of(2);
 
     // Optimal algebraic construction: when an HSOLSSOM build succeeds it is
     // provably optimal on *all four* objectives at once (both hard ledgers
     // saturated, full courts, both same-gender excesses at floor), so it
     // dominates every heuristic candidate under any emphasis — return it
-    // directly. Its native layout is n rounds of n/2 games; if the caller has
-    // fewer courts than n/2 we keep the (still-optimal) game set and repack.
+    // directly. Its native layout is n rounds of n/2 games; if any round has
+    // fewer courts than n/2 we keep the (still-optimal) game set and lay it
+    // out afresh. A night of a season is optimal only for the night, so there
+    // it joins the other candidates instead.
+    let mut candidates: Vec<Schedule> = Vec::new();
     if balanced_even {
-        if let Some(sched) = hsolssom(roster) {
-            if verify(&sched, roster, courts).is_legal() {
-                if courts as usize >= n / 2 {
-                    return sched;
+        if let Some(sched) = hsolssom_within(roster, &budget.portion(0.25)) {
+            if verify(&sched, roster, (n / 2) as u16).is_legal() {
+                let sched = lay_out(sched, n, courts, budget, &mut rng);
+                if night.season().is_none() {
+                    return Seeded::Optimal(sched);
                 }
-                let games: Vec<Game> = sched.all_games().copied().collect();
-                return repack(&games, courts, &mut rng);
+                candidates.push(sched);
             }
         }
     }
 
     // Otherwise assemble candidate schedules spanning the frontier and let the
     // emphasis-weighted selection choose.
-    let mut candidates: Vec<Schedule> = Vec::new();
 
     // Court-first candidate: keep its own fully-packed round structure (the
     // low-round corner) — repacking it blindly would only scatter it.
-    let cf = court_first(roster, courts, 200, &mut rng);
+    // A time budget is split across the phases in proportion to their usual
+    // share of the work; unlimited budgets stay unlimited.
+    let cf = court_first(night, courts, 200, obj, &budget.portion(0.1), &mut rng);
     candidates.push(Schedule::new(cf.into_iter().map(Round::new).collect()));
 
     // Reflection candidate (balanced even n): deterministic, legal, fully
     // packed — a strong court-emphasis option when HSOLSSOM isn't available.
     if balanced_even {
         if let Some(refl) = reflection(roster) {
-            if courts as usize >= n / 2 {
-                candidates.push(refl);
-            } else {
-                let games: Vec<Game> = refl.all_games().copied().collect();
-                candidates.push(repack(&games, courts, &mut rng));
-            }
+            candidates.push(lay_out(refl, n, courts, &budget.portion(0.05), &mut rng));
         }
     }
+    Seeded::Candidates(candidates)
+}
 
-    // Variety candidates: ruin-and-recreate (low same-gender corner), which
-    // have no round structure of their own, so repack them tightly.
-    let starts = 6u32;
-    let per = (ls_iters / starts).max(1);
-    for _ in 0..starts {
-        let rounds = anneal(roster, courts, per, &mut rng);
-        let games: Vec<Game> = rounds.into_iter().flatten().collect();
-        candidates.push(repack(&games, courts, &mut rng));
+/// A construction of `n/2`-game rounds fitted to `courts`: as built when every
+/// round has the courts, its rounds split across the leaner ones when some
+/// round does, and repacked otherwise.
+fn lay_out(
+    sched: Schedule,
+    n: usize,
+    courts: &CourtPlan,
+    budget: &Budget,
+    rng: &mut StdRng,
+) -> Schedule {
+    if courts.fewest() as usize >= n / 2 {
+        sched
+    } else if courts.most() as usize >= n / 2 {
+        split_rounds(sched, courts)
+    } else {
+        let games: Vec<Game> = sched.all_games().copied().collect();
+        repack(&games, courts, REPACK_TRIES, budget, rng)
     }
+}
 
-    // Select using the verifier's *true* stats (same oracle as the output), so
-    // the choice is consistent. `round_weight` slides court-fullness vs repeats.
+/// Select using the verifier's *true* stats (same oracle as the output), so
+/// the choice is consistent. The round weight slides court-fullness vs repeats.
+/// In a season, each candidate also pays for what it repeats from the earlier
+/// nights, read off a copy of the empty ledger `night`.
+fn select(
+    candidates: Vec<Schedule>,
+    night: &LiveReport,
+    courts: &CourtPlan,
+    obj: &Objective,
+) -> Schedule {
     let mut best: Option<((i64, f64), Schedule)> = None;
     for cand in candidates {
-        let report = verify(&cand, roster, courts);
-        let cost = selection_cost(&report, round_weight);
-        if best.as_ref().map_or(true, |(bc, _)| cost < *bc) {
+        let report = verify(&cand, night.roster(), courts);
+        let mut cost = selection_cost(&report, obj);
+        if night.season().is_some() {
+            let mut led = night.clone();
+            cand.rounds.iter().for_each(|r| led.add_round(&r.games));
+            let stale = Stats::of(&led);
+            cost.1 += obj.history * stale.stale as f64 + obj.excess * stale.stale_met as f64;
+        }
+        if best.as_ref().is_none_or(|(bc, _)| cost < *bc) {
             best = Some((cost, cand));
         }
     }
-
     best.expect("at least one candidate").1
 }
 
+/// The chosen schedule, repacked exactly ([`pack_exact`]) when the randomized
+/// repack left rounds on the table. The exact packer knows only uniform
+/// courts, so a varying plan keeps the randomized packing.
+fn tighten(sched: Schedule, roster: Roster, courts: &CourtPlan, budget: &Budget) -> Schedule {
+    if !courts.is_uniform() {
+        return sched;
+    }
+    pack_exact(&sched, roster, courts.most(), budget).schedule
+}
+
+/// A stepwise handle on [`optimize`] for callers that must stay responsive —
+/// the browser above all, which has one thread and no way to interrupt a
+/// blocking call. Each [`Optimizer::step`] does as much work as its budget
+/// allows and returns; between steps the caller can read [`Optimizer::best`]
+/// and [`Optimizer::progress`], or simply stop stepping.
+///
+/// The starts run one after another on this thread. If the first step's
+/// budget covers the construction phase, the finished result equals
+/// [`optimize`]'s for the same arguments, however the later steps are sliced.
+pub struct Optimizer {
+    roster: Roster,
+    courts: CourtPlan,
+    objective: Objective,
+    seed: u64,
+    per: u32,
+    seeded: Option<Seeded>,
+    /// The start being annealed, with its index.
+    current: Option<(usize, Anneal)>,
+    next_start: usize,
+    progress: Option<Progress>,
+}
+
+impl Optimizer {
+    pub fn new(
+        roster: Roster,
+        courts: impl Into<CourtPlan>,
+        ls_iters: u32,
+        objective: &Objective,
+        seed: u64,
+    ) -> Self {
+        Optimizer {
+            roster,
+            courts: courts.into(),
+            objective: *objective,
+            seed,
+            per: (ls_iters / STARTS as u32).max(1),
+            seeded: None,
+            current: None,
+            next_start: 0,
+            progress: None,
+        }
+    }
+
+    /// Work until `budget` expires or the optimization finishes; returns
+    /// whether it has finished. The first step runs only the construction
+    /// phase (constructors and the court-first builder) under its budget.
+    pub fn step(&mut self, budget: &Budget) -> bool {
+        let (roster, courts) = (self.roster, &self.courts);
+        let candidates = match &mut self.seeded {
+            None => {
+                let night = LiveReport::new(roster, courts);
+                let seeded = seed_candidates(&night, courts, budget, &self.objective, self.seed);
+                self.seeded = Some(seeded);
+                return self.is_done();
+            }
+            Some(Seeded::Optimal(_)) => return true,
+            Some(Seeded::Candidates(c)) => c,
+        };
+        while !budget.expired() {
+            let (i, run) = match &mut self.current {
+                Some(cur) => cur,
+                None if self.next_start < STARTS => {
+                    let i = self.next_start;
+                    self.next_start += 1;
+                    let rng = StdRng::seed_from_u64(derive_seed(self.seed, i));
+                    let (obj, per) = (self.objective, Some(self.per));
+                    let night = LiveReport::new(roster, courts);
+                    let run = Anneal::new(&night, courts, obj, per, Start::Cold, budget, rng);
+                    self.current.insert((i, run))
+                }
+                None => return true,
+            };
+            let i = *i;
+            let progress = &mut self.progress;
+            let done = run.run(budget, &mut |a| *progress = Some(a.progress(i, STARTS)));
+            *progress = Some(run.progress(i, STARTS));
+            if !done {
+                return false;
+            }
+            let (_, mut run) = self.current.take().expect("a start is running");
+            let games = run.best_games();
+            candidates.push(repack(&games, courts, REPACK_TRIES, &Budget::unlimited(), &mut run.rng));
+        }
+        self.is_done()
+    }
+
+    pub fn is_done(&self) -> bool {
+        match &self.seeded {
+            Some(Seeded::Optimal(_)) => true,
+            Some(Seeded::Candidates(_)) => self.current.is_none() && self.next_start == STARTS,
+            None => false,
+        }
+    }
+
+    /// The latest progress report, if any start has run yet.
+    pub fn progress(&self) -> Option<Progress> {
+        self.progress
+    }
+
+    /// The best schedule so far: the finished result once
+    /// [`is_done`](Self::is_done), otherwise the best of the candidates ready
+    /// so far and the running start's current best. Empty before the first
+    /// step.
+    pub fn best(&self) -> Schedule {
+        match self.interim() {
+            Some(Seeded::Optimal(sched)) => sched,
+            Some(Seeded::Candidates(c)) => {
+                let night = LiveReport::new(self.roster, &self.courts);
+                let sched = select(c, &night, &self.courts, &self.objective);
+                if !self.is_done() {
+                    return sched;
+                }
+                tighten(sched, self.roster, &self.courts, &Budget::unlimited())
+            }
+            None => Schedule::default(),
+        }
+    }
+
+    /// The Pareto frontier (see [`pareto`]) of the same candidates
+    /// [`best`](Self::best) chooses from. Empty before the first step.
+    pub fn frontier(&self) -> Vec<(Schedule, Report)> {
+        match self.interim() {
+            Some(Seeded::Optimal(sched)) => pareto(vec![sched], self.roster, &self.courts),
+            Some(Seeded::Candidates(c)) => pareto(c, self.roster, &self.courts),
+            None => Vec::new(),
+        }
+    }
+
+    /// The candidates so far, plus a quick repack of the running start — good
+    /// enough for a preview.
+    fn interim(&self) -> Option<Seeded> {
+        let mut candidates = match self.seeded.as_ref()? {
+            Seeded::Optimal(sched) => return Some(Seeded::Optimal(sched.clone())),
+            Seeded::Candidates(c) => c.clone(),
+        };
+        if let Some((_, run)) = &self.current {
+            let mut rng = run.rng.clone();
+            let unlimited = Budget::unlimited();
+            candidates.push(repack(&run.best_games(), &self.courts, 20, &unlimited, &mut rng));
+        }
+        Some(Seeded::Candidates(candidates))
+    }
+}
+
 /// Selection cost for a finished schedule: maximize games first (as `-games`),
-/// then a `round_weight`-tunable blend of court utilization and same-gender
-/// repeats. Uses the verifier's real, repacked stats.
-fn selection_cost(r: &Report, round_weight: f64) -> (i64, f64) {
-    let soft = round_weight * r.rounds as f64
-        + W_MAX * (r.man_max_meetings + r.woman_max_meetings) as f64
-        + W_EXCESS * (r.man_repeat_excess + r.woman_repeat_excess) as f64
-        + W_SPREAD * r.participation_spread() as f64;
+/// then the objective's blend of court utilization and same-gender repeats.
+/// Uses the verifier's real, repacked stats.
+fn selection_cost(r: &Report, obj: &Objective) -> (i64, f64) {
+    let soft = obj.rounds * r.rounds as f64
+        + obj.max_meetings * (r.man_max_meetings + r.woman_max_meetings) as f64
+        + obj.excess * (r.man_repeat_excess + r.woman_repeat_excess) as f64
+        + obj.spread * r.participation_spread() as f64;
     (-(r.games as i64), soft)
 }
@@ -0,0 +1,364 @@
+//! League seasons: partnership and opposition history across nights.
+//!
+//! A ladder league meets weekly with mostly the same people, so a night that
+//! is repeat-free on its own can still pair players who partnered last week.
+//! A [`Season`] keeps the cumulative ledgers of every night recorded so far.
+//! The optimizers build the next night against them —
+//! [`optimize_in_season`](crate::search::optimize_in_season) and the
+//! `_in_season` modes of [`target`](crate::target) score each repeat of an
+//! earlier night's pairing (see [`Objective::history`]) alongside the night's
+//! own terms.
+//!
+//! [`Season::fit`] is an optional post-pass on a finished night: it relabels
+//! the night's men and women so its games land on the pairs that have met
+//! least over the season, and an uneven night's extra games go to whoever
+//! has played least. Every metric the optimizers score within a night is
+//! blind to names, so the night itself — repeats, byes, rounds, court
+//! rotation — is unchanged.
+//!
+//! [`Season::report`] answers the season-level questions ("who has never
+//! partnered whom after 8 weeks"), and [`Season::save`] / [`Season::load`]
+//! keep the ledgers in a small text file between nights.
+
+use crate::model::{Game, Man, Player, Roster, Round, Schedule, Team, Woman};
+use crate::objective::Objective;
+use crate::parallel::{self, derive_seed};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use std::path::Path;
+
+/// Cumulative ledgers over the nights of one league's roster.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Season {
+    roster: Roster,
+    nights: usize,
+    /// Games played, men then women.
+    played: Vec<usize>,
+    /// `[m · W + w]`: times `m` and `w` have partnered.
+    partnered: Vec<usize>,
+    /// `[m · W + w]`: times `m` has faced `w`.
+    opposed: Vec<usize>,
+    /// `[a · M + b]`, symmetric: times men `a` and `b` have met.
+    men_met: Vec<usize>,
+    /// `[x · W + y]`, symmetric: times women `x` and `y` have met.
+    women_met: Vec<usize>,
+}
+
+/// The season so far, as [`Season::report`] summarizes it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SeasonReport {
+    pub roster: Roster,
+    pub nights: usize,
+    pub games_per_man: Vec<usize>,
+    pub games_per_woman: Vec<usize>,
+    /// Man–woman pairs that have never been partners.
+    pub never_partnered: Vec<(Man, Woman)>,
+    /// Man–woman pairs that have never been on opposite sides of a net.
+    pub never_opposed: Vec<(Man, Woman)>,
+    /// Partnerships played beyond the first, summed over all pairs.
+    pub partner_repeats: usize,
+    /// Mixed oppositions played beyond the first, summed over all pairs.
+    pub mixed_repeats: usize,
+    /// Man–man and woman–woman meetings beyond the first.
+    pub man_repeats: usize,
+    pub woman_repeats: usize,
+}
+
+/// Independent relabeling descents [`Season::fit`] runs; the first starts
+/// from the night's own labels.
+const FIT_STARTS: usize = 16;
+
+impl Season {
+    /// A season with no nights yet.
+    pub fn new(roster: Roster) -> Self {
+        let (m, w) = (roster.men as usize, roster.women as usize);
+        Season {
+            roster,
+            nights: 0,
+            played: vec![0; m + w],
+            partnered: vec![0; m * w],
+            opposed: vec![0; m * w],
+            men_met: vec![0; m * m],
+            women_met: vec![0; w * w],
+        }
+    }
+
+    pub fn roster(&self) -> Roster {
+        self.roster
+    }
+
+    /// Nights recorded so far.
+    pub fn nights(&self) -> usize {
+        self.nights
+    }
+
+    /// Add a night's games to the ledgers. `night` must be for this season's
+    /// roster.
+    pub fn record(&mut self, night: &Schedule) {
+        let (m, w) = (self.roster.men as usize, self.roster.women as usize);
+        for g in night.all_games() {
+            for p in g.players() {
+                let seat = self.seat(p);
+                self.played[seat] += 1;
+            }
+            for (a, x) in g.partnerships() {
+                self.partnered[a.0 as usize * w + x.0 as usize] += 1;
+            }
+            for (a, x) in g.mixed_opps() {
+                self.opposed[a.0 as usize * w + x.0 as usize] += 1;
+            }
+            let ((a, b), (x, y)) = (g.men(), g.women());
+            let (a, b, x, y) = (a.0 as usize, b.0 as usize, x.0 as usize, y.0 as usize);
+            self.men_met[a * m + b] += 1;
+            self.men_met[b * m + a] += 1;
+            self.women_met[x * w + y] += 1;
+            self.women_met[y * w + x] += 1;
+        }
+        self.nights += 1;
+    }
+
+    pub fn played(&self, p: Player) -> usize {
+        self.played[self.seat(p)]
+    }
+
+    pub fn partnered(&self, m: Man, w: Woman) -> usize {
+        self.partnered[m.0 as usize * self.roster.women as usize + w.0 as usize]
+    }
+
+    pub fn opposed(&self, m: Man, w: Woman) -> usize {
+        self.opposed[m.0 as usize * self.roster.women as usize + w.0 as usize]
+    }
+
+    pub fn men_met(&self, a: Man, b: Man) -> usize {
+        self.men_met[a.0 as usize * self.roster.men as usize + b.0 as usize]
+    }
+
+    pub fn women_met(&self, x: Woman, y: Woman) -> usize {
+        self.women_met[x.0 as usize * self.roster.women as usize + y.0 as usize]
+    }
+
+    fn seat(&self, p: Player) -> usize {
+        self.roster.seat(p)
+    }
+
+    /// `night` (for this season's roster) with its men and women relabeled to
+    /// suit the season: weighted like Part 2, each game costs
+    /// [`repeat`](Objective::repeat) per earlier partnership or mixed
+    /// opposition it repeats, [`fairness`](Objective::fairness) per season
+    /// game its players already have, and
+    /// [`same_gender`](Objective::same_gender) per earlier same-gender
+    /// meeting. The relabeling is a local search over swaps of two men or two
+    /// women from several starts, never worse than the labels given;
+    /// deterministic for a given `seed`.
+    pub fn fit(&self, night: &Schedule, objective: &Objective, seed: u64) -> Schedule {
+        let games: Vec<Game> = night.all_games().copied().collect();
+        let (m, w) = (self.roster.men as usize, self.roster.women as usize);
+        let mut touch = vec![Vec::new(); m + w];
+        for (i, g) in games.iter().enumerate() {
+            for p in g.players() {
+                touch[self.seat(p)].push(i);
+            }
+        }
+        let fit = Fit { season: self, objective, games: &games, touch };
+        let starts = parallel::run_indexed(FIT_STARTS, |i| {
+            let mut labels: Vec<u16> = (0..m as u16).chain(0..w as u16).collect();
+            if i > 0 {
+                let mut rng = StdRng::seed_from_u64(derive_seed(seed, i));
+                labels[..m].shuffle(&mut rng);
+                labels[m..].shuffle(&mut rng);
+            }
+            fit.descend(&mut labels);
+            (fit.total(&labels), labels)
+        });
+        let mut best = &starts[0];
+        for start in &starts[1..] {
+            if start.0 < best.0 - 1e-9 {
+                best = start;
+            }
+        }
+        let labels = &best.1;
+        let rounds = night.rounds.iter().map(|round| {
+            let games = round.games.iter().map(|g| relabel(g, labels, m)).collect();
+            Round::with_courts(games, round.courts.clone())
+        });
+        Schedule::new(rounds.collect())
+    }
+
+    /// The season's totals and the pairs still to meet.
+    pub fn report(&self) -> SeasonReport {
+        let m = self.roster.men as usize;
+        let women = || self.roster.women_iter();
+        let pairs = || self.roster.men_iter().flat_map(|a| women().map(move |x| (a, x)));
+        let excess = |counts: &[usize]| counts.iter().map(|&c| c.saturating_sub(1)).sum::<usize>();
+        SeasonReport {
+            roster: self.roster,
+            nights: self.nights,
+            games_per_man: self.played[..m].to_vec(),
+            games_per_woman: self.played[m..].to_vec(),
+            never_partnered: pairs().filter(|&(a, x)| self.partnered(a, x) == 0).collect(),
+            never_opposed: pairs().filter(|&(a, x)| self.opposed(a, x) == 0).collect(),
+            partner_repeats: excess(&self.partnered),
+            mixed_repeats: excess(&self.opposed),
+            // Each same-gender pair is stored twice.
+            man_repeats: excess(&self.men_met) / 2,
+            woman_repeats: excess(&self.women_met) / 2,
+        }
+    }
+
+    /// The ledgers as text: a `season M W` header, the night count, then one
+    /// line per ledger row.
+    pub fn to_text(&self) -> String {
+        let (m, w) = (self.roster.men as usize, self.roster.women as usize);
+        let mut out = format!("season {} {}\nnights {}\nplayed\n", m, w, self.nights);
+        push_rows(&mut out, &self.played, m + w);
+        out.push_str("partnered\n");
+        push_rows(&mut out, &self.partnered, w);
+        out.push_str("opposed\n");
+        push_rows(&mut out, &self.opposed, w);
+        out.push_str("men-met\n");
+        push_rows(&mut out, &self.men_met, m);
+        out.push_str("women-met\n");
+        push_rows(&mut out, &self.women_met, w);
+        out
+    }
+
+    /// Parse [`to_text`](Self::to_text)'s output.
+    pub fn from_text(text: &str) -> Result<Self, String> {
+        let mut lines = text.lines().map(str::trim).filter(|l| !l.is_empty());
+        let header = lines.next().unwrap_or_default();
+        let roster = match header.split_whitespace().collect::<Vec<_>>()[..] {
+            ["season", m, w] => match (m.parse(), w.parse()) {
+                (Ok(m), Ok(w)) => Roster::new(m, w),
+                _ => return Err(format!("bad season header `{}`", header)),
+            },
+            _ => return Err("not a season file (no `season M W` header)".to_string()),
+        };
+        let nights = lines
+            .next()
+            .and_then(|l| l.strip_prefix("nights "))
+            .and_then(|n| n.trim().parse().ok())
+            .ok_or("season file is missing its `nights N` line")?;
+        let (m, w) = (roster.men as usize, roster.women as usize);
+        Ok(Season {
+            roster,
+            nights,
+            played: read_rows(&mut lines, "played", 1, m + w)?,
+            partnered: read_rows(&mut lines, "partnered", m, w)?,
+            opposed: read_rows(&mut lines, "opposed", m, w)?,
+            men_met: read_rows(&mut lines, "men-met", m, m)?,
+            women_met: read_rows(&mut lines, "women-met", w, w)?,
+        })
+    }
+
+    /// Write the season to `path`, replacing it.
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        std::fs::write(path, self.to_text()).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    /// Read a season written by [`save`](Self::save).
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        Season::from_text(&text).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+}
+
+/// The relabeling search for one night: `labels[i]` is the season player
+/// standing in for the night's man `i` (`i < M`) or woman `i − M`.
+struct Fit<'a> {
+    season: &'a Season,
+    objective: &'a Objective,
+    games: &'a [Game],
+    /// The games each night seat plays in, men then women.
+    touch: Vec<Vec<usize>>,
+}
+
+impl Fit<'_> {
+    fn cost(&self, g: &Game, labels: &[u16]) -> f64 {
+        let s = self.season;
+        let m = s.roster.men as usize;
+        let g = relabel(g, labels, m);
+        let repeats: usize = g.partnerships().iter().map(|&(a, x)| s.partnered(a, x)).sum::<usize>()
+            + g.mixed_opps().iter().map(|&(a, x)| s.opposed(a, x)).sum::<usize>();
+        let played: usize = g.players().iter().map(|&p| s.played(p)).sum();
+        let ((a, b), (x, y)) = (g.men(), g.women());
+        let met = s.men_met(a, b) + s.women_met(x, y);
+        let obj = self.objective;
+        obj.repeat * repeats as f64 + obj.fairness * played as f64 + obj.same_gender * met as f64
+    }
+
+    fn total(&self, labels: &[u16]) -> f64 {
+        self.games.iter().map(|g| self.cost(g, labels)).sum()
+    }
+
+    /// Cost of the games seats `i` and `j` play in, each counted once.
+    fn local(&self, i: usize, j: usize, labels: &[u16]) -> f64 {
+        let both = self.touch[j].iter().filter(|gi| !self.touch[i].contains(gi));
+        self.touch[i].iter().chain(both).map(|&gi| self.cost(&self.games[gi], labels)).sum()
+    }
+
+    /// Swap labels within a gender while any swap lowers the cost.
+    fn descend(&self, labels: &mut [u16]) {
+        let m = self.season.roster.men as usize;
+        let ranges = [0..m, m..labels.len()];
+        loop {
+            let mut improved = false;
+            for range in ranges.clone() {
+                for i in range.clone() {
+                    for j in i + 1..range.end {
+                        if self.touch[i].is_empty() && self.touch[j].is_empty() {
+                            continue;
+                        }
+                        let before = self.local(i, j, labels);
+                        labels.swap(i, j);
+                        if self.local(i, j, labels) < before - 1e-9 {
+                            improved = true;
+                        } else {
+                            labels.swap(i, j);
+                        }
+                    }
+                }
+            }
+            if !improved {
+                return;
+            }
+        }
+    }
+}
+
+/// `g` with each player replaced by their label (`m` men first).
+fn relabel(g: &Game, labels: &[u16], m: usize) -> Game {
+    let team = |t: Team| {
+        Team::new(Man(labels[t.man.0 as usize]), Woman(labels[m + t.woman.0 as usize]))
+    };
+    Game::new(team(g.a), team(g.b))
+}
+
+fn push_rows(out: &mut String, values: &[usize], width: usize) {
+    for row in values.chunks(width.max(1)) {
+        let row: Vec<String> = row.iter().map(usize::to_string).collect();
+        out.push_str(&row.join(" "));
+        out.push('\n');
+    }
+}
+
+fn read_rows<'a>(
+    lines: &mut impl Iterator<Item = &'a str>,
+    name: &str,
+    rows: usize,
+    width: usize,
+) -> Result<Vec<usize>, String> {
+    if lines.next() != Some(name) {
+        return Err(format!("season file is missing its `{}` section", name));
+    }
+    let mut values = Vec::with_capacity(rows * width);
+    for _ in 0..rows {
+        let line = lines.next().ok_or_else(|| format!("`{}` is cut short", name))?;
+        let row: Result<Vec<usize>, _> = line.split_whitespace().map(str::parse).collect();
+        match row {
+            Ok(row) if row.len() == width => values.extend(row),
+            _ => return Err(format!("bad `{}` row `{}`", name, line)),
+        }
+    }
+    Ok(values)
+}
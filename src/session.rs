@@ -0,0 +1,109 @@
+//! Session timing: how many rounds fit a booked block of court time.
+//!
+//! Organizers think in clock time — "the gym is ours 7–9pm, a game takes
+//! about 12 minutes and changing courts 2 more". A [`Session`] turns that into
+//! a round count and a projected start/end per round, which
+//! [`by_session`](crate::target::by_session) then fills with the best
+//! schedule of exactly that many rounds.
+//!
+//! All times are whole minutes. Round times are measured from the session
+//! start; [`clock`] renders them against [`Session::start`] for display.
+
+/// A block of court time. Built with [`Session::new`] and the builder methods;
+/// the default has no changeover, no break, and starts at midnight.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Session {
+    /// Time of day the session starts, in minutes after midnight (display only).
+    pub start: u32,
+    /// Minutes the courts are booked for.
+    pub length: u32,
+    /// Minutes per round of play.
+    pub game: u32,
+    /// Minutes between consecutive rounds.
+    pub changeover: u32,
+    /// An optional rest: taken before the first round that would start at or
+    /// after `at` minutes in, lasting `length` minutes.
+    pub pause: Option<Break>,
+}
+
+/// A rest in the middle of a session.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Break {
+    /// Minutes into the session.
+    pub at: u32,
+    pub length: u32,
+}
+
+impl Session {
+    /// `length` minutes of court time, `game` minutes per round.
+    pub fn new(length: u32, game: u32) -> Self {
+        Session { start: 0, length, game, changeover: 0, pause: None }
+    }
+
+    /// Also wait `minutes` between rounds.
+    pub fn changeover(mut self, minutes: u32) -> Self {
+        self.changeover = minutes;
+        self
+    }
+
+    /// Also start the clock at `minute_of_day` (e.g. `19 * 60` for 7pm).
+    pub fn starting_at(mut self, minute_of_day: u32) -> Self {
+        self.start = minute_of_day;
+        self
+    }
+
+    /// Also break for `length` minutes around `at` minutes in.
+    pub fn with_break(mut self, at: u32, length: u32) -> Self {
+        self.pause = Some(Break { at, length });
+        self
+    }
+
+    /// Each round that fits, as `(start, end)` minutes from the session start.
+    /// Empty if a single game does not fit (or takes no time at all).
+    pub fn round_times(&self) -> Vec<(u32, u32)> {
+        let mut times = Vec::new();
+        if self.game == 0 {
+            return times;
+        }
+        let mut pause = self.pause;
+        let mut t = 0u32;
+        loop {
+            if let Some(b) = pause.filter(|b| t >= b.at) {
+                t += b.length;
+                pause = None;
+            }
+            if t + self.game > self.length {
+                break;
+            }
+            times.push((t, t + self.game));
+            t += self.game + self.changeover;
+        }
+        times
+    }
+
+    /// Rounds that fit the session.
+    pub fn rounds(&self) -> usize {
+        self.round_times().len()
+    }
+
+    /// The index of the first round after the break, if the break is taken.
+    pub fn break_before(&self) -> Option<usize> {
+        let b = self.pause?;
+        self.round_times().iter().position(|&(s, _)| s >= b.at)
+    }
+}
+
+/// `minutes` after `session`'s start as a `HH:MM` time of day.
+pub fn clock(session: &Session, minutes: u32) -> String {
+    let t = (session.start + minutes) % (24 * 60);
+    format!("{:02}:{:02}", t / 60, t % 60)
+}
+
+/// Parse a time of day, `HH:MM` (24-hour), into minutes after midnight.
+pub fn parse_clock(s: &str) -> Result<u32, String> {
+    let (h, m) = s.trim().split_once(':').ok_or_else(|| format!("time `{s}` is not HH:MM"))?;
+    match (h.parse::<u32>(), m.parse::<u32>()) {
+        (Ok(h), Ok(m)) if h < 24 && m < 60 => Ok(h * 60 + m),
+        _ => Err(format!("time `{s}` is not HH:MM")),
+    }
+}
@@ -0,0 +1,214 @@
+//! Productive byes: side games for the players a round leaves out.
+//!
+//! When a round has more players than its courts seat, three to seven of them
+//! typically sit and wait. [`fill_byes`] gives them something to do instead:
+//! same-gender doubles or singles on any court the round leaves spare, and a
+//! drill for whoever is still left, as [`SideOptions`] allows.
+//!
+//! Side games keep a ledger of their own — how many each player has had, and
+//! how often any two players have met in one — so they rotate like the byes
+//! do: a round's side games go to whoever has had fewest, then to players who
+//! have met least. They never enter the mixed-doubles ledgers, so every
+//! partnership and opposition guarantee of the schedule stands;
+//! [`verify`](crate::verify::verify) counts them in fields of their own and
+//! only checks that they fit the round (players on a bye, courts free).
+//!
+//! Fill byes last: rebuilding a round's games — rotating its courts with
+//! [`assign_courts`](crate::rotation::assign_courts), relabeling it with
+//! [`Season::fit`](crate::season::Season::fit) — drops its side games.
+
+use crate::model::{CourtPlan, Player, Roster, Round, Schedule, SideGame};
+use std::collections::HashSet;
+
+/// Which side activities [`fill_byes`] may use. The default allows all three.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SideOptions {
+    /// Same-gender doubles on a spare court.
+    pub doubles: bool,
+    /// Singles on a spare court.
+    pub singles: bool,
+    /// A drill, off court, for everyone no side game takes.
+    pub drills: bool,
+}
+
+impl Default for SideOptions {
+    fn default() -> Self {
+        SideOptions { doubles: true, singles: true, drills: true }
+    }
+}
+
+impl SideOptions {
+    /// Parse a comma-separated list of `doubles`, `singles` and `drill`, or
+    /// `all`.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut options = SideOptions { doubles: false, singles: false, drills: false };
+        for item in spec.split(',').map(str::trim) {
+            match item {
+                "all" => options = SideOptions::default(),
+                "doubles" => options.doubles = true,
+                "singles" => options.singles = true,
+                "drill" | "drills" => options.drills = true,
+                _ => return Err(format!("side activity `{item}` is not doubles, singles, drill or all")),
+            }
+        }
+        Ok(options)
+    }
+}
+
+/// Candidates considered per side game: the players with the fewest side
+/// games so far. Eight keep the search to 70 foursomes.
+const POOL: usize = 8;
+
+/// The side-game ledger: side games per player, and meetings per pair.
+struct Ledger {
+    roster: Roster,
+    /// Side games played, men then women.
+    played: Vec<usize>,
+    /// `[p · n + q]`, symmetric: times `p` and `q` have shared a side game.
+    met: Vec<usize>,
+}
+
+impl Ledger {
+    fn new(roster: Roster) -> Self {
+        let n = roster.total_players();
+        Ledger { roster, played: vec![0; n], met: vec![0; n * n] }
+    }
+
+    fn seat(&self, p: Player) -> usize {
+        self.roster.seat(p)
+    }
+
+    fn met(&self, p: Player, q: Player) -> usize {
+        self.met[self.seat(p) * self.played.len() + self.seat(q)]
+    }
+
+    /// What seating `players` together costs: side games they have had, then
+    /// meetings among them.
+    fn cost(&self, players: &[Player]) -> (usize, usize) {
+        let played = players.iter().map(|&p| self.played[self.seat(p)]).sum();
+        let mut met = 0;
+        for (i, &p) in players.iter().enumerate() {
+            for &q in &players[i + 1..] {
+                met += self.met(p, q);
+            }
+        }
+        (played, met)
+    }
+
+    fn record(&mut self, players: &[Player]) {
+        let n = self.played.len();
+        for (i, &p) in players.iter().enumerate() {
+            let sp = self.seat(p);
+            self.played[sp] += 1;
+            for &q in &players[i + 1..] {
+                let sq = self.seat(q);
+                self.met[sp * n + sq] += 1;
+                self.met[sq * n + sp] += 1;
+            }
+        }
+    }
+
+    /// The `idle` players `keep` accepts with the fewest side games, at most
+    /// [`POOL`] of them.
+    fn pool(&self, idle: &[Player], keep: impl Fn(&Player) -> bool) -> Vec<Player> {
+        let mut pool: Vec<Player> = idle.iter().copied().filter(keep).collect();
+        pool.sort_by_key(|&p| (self.played[self.seat(p)], p));
+        pool.truncate(POOL);
+        pool
+    }
+
+    /// The cheapest same-gender foursome among `idle`, if either gender has
+    /// four to spare.
+    fn best_doubles(&self, idle: &[Player]) -> Option<[Player; 4]> {
+        let mut best: Option<((usize, usize), [Player; 4])> = None;
+        for men in [true, false] {
+            let pool = self.pool(idle, |p| matches!(p, Player::M(_)) == men);
+            let k = pool.len();
+            for a in 0..k {
+                for b in a + 1..k {
+                    for c in b + 1..k {
+                        for d in c + 1..k {
+                            let four = [pool[a], pool[b], pool[c], pool[d]];
+                            let cost = self.cost(&four);
+                            if best.is_none_or(|(bc, _)| cost < bc) {
+                                best = Some((cost, four));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        best.map(|(_, four)| four)
+    }
+
+    /// The cheapest pair among `idle`, same gender preferred on a tie.
+    fn best_singles(&self, idle: &[Player]) -> Option<[Player; 2]> {
+        let pool = self.pool(idle, |_| true);
+        let mut best: Option<((usize, usize, bool), [Player; 2])> = None;
+        for (i, &p) in pool.iter().enumerate() {
+            for &q in &pool[i + 1..] {
+                let (played, met) = self.cost(&[p, q]);
+                let mixed = matches!(p, Player::M(_)) != matches!(q, Player::M(_));
+                let cost = (played, met, mixed);
+                if best.is_none_or(|(bc, _)| cost < bc) {
+                    best = Some((cost, [p, q]));
+                }
+            }
+        }
+        best.map(|(_, two)| two)
+    }
+
+    /// Side games for `round`'s byes, on its spare courts among the first
+    /// `open`, recorded as they are chosen.
+    fn fill(&mut self, round: &Round, open: u16, options: &SideOptions) -> Vec<SideGame> {
+        let busy: HashSet<Player> = round.active_players().into_iter().collect();
+        let roster = self.roster;
+        let everyone = roster.men_iter().map(Player::M).chain(roster.women_iter().map(Player::W));
+        let mut idle: Vec<Player> = everyone.filter(|p| !busy.contains(p)).collect();
+        let taken: HashSet<u16> = (0..round.games.len()).map(|i| round.court(i)).collect();
+        let mut side = Vec::new();
+        for court in (0..open).filter(|c| !taken.contains(c)) {
+            let doubles = options.doubles.then(|| self.best_doubles(&idle)).flatten();
+            let game = match doubles {
+                Some([a, b, c, d]) => SideGame::Doubles { court, a: [a, b], b: [c, d] },
+                None => match options.singles.then(|| self.best_singles(&idle)).flatten() {
+                    Some([a, b]) => SideGame::Singles { court, a, b },
+                    None => break,
+                },
+            };
+            let players = game.players();
+            self.record(&players);
+            idle.retain(|p| !players.contains(p));
+            side.push(game);
+        }
+        if options.drills && !idle.is_empty() {
+            side.push(SideGame::Drill { players: idle });
+        }
+        side
+    }
+}
+
+/// `schedule` with its byes put to use: each round gets side games on the
+/// courts `courts` offers it that its games leave free, and a drill for the
+/// rest, as `options` allows. Side games rotate over the whole schedule (see
+/// the [module docs](self)); the mixed-doubles games are untouched.
+pub fn fill_byes(
+    schedule: &Schedule,
+    roster: Roster,
+    courts: impl Into<CourtPlan>,
+    options: &SideOptions,
+) -> Schedule {
+    let courts = courts.into();
+    let mut ledger = Ledger::new(roster);
+    let rounds = schedule
+        .rounds
+        .iter()
+        .enumerate()
+        .map(|(r, round)| {
+            let mut round = round.clone();
+            round.side = ledger.fill(&round, courts.at(r), options);
+            round
+        })
+        .collect();
+    Schedule::new(rounds)
+}
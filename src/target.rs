@@ -2,11 +2,13 @@
 //!
 //! Part 1 maximizes games with the once-rules hard. Part 2 fixes the amount of
 //! play instead and makes those rules **soft** — minimized toward their floor
-//! rather than forbidden. Two modes, both reducing to one primitive (build a
+//! rather than forbidden. The modes all reduce to one primitive (build a
 //! target number of games):
 //!
 //! * [`by_games_per_player`] — everyone plays (about) `N` games.
+//! * [`by_games_per_gender`] — every man plays exactly `A`, every woman `B`.
 //! * [`by_total_games`] — cap the schedule at exactly `G` games.
+//! * [`by_quotas`] — each player plays within their own [`Quotas`] range.
 //!
 //! Below the game ceiling there is slack, so partnerships and mixed
 //! oppositions still never repeat (their floor is 0); above it, repeats are
@@ -14,149 +16,346 @@
 //! fair (byes rotate to whoever has played least), and when the target is
 //! exactly the full round-robin the balanced case delegates to Part 1 so it
 //! still gets the optimal algebraic construction.
+//!
+//! The greedy builds are then polished by annealing, with Part 1's move:
+//! ruin a round or two and refill them. A trial is scored on the same
+//! lexicographic terms — hard repeats, then same-gender meetings, then
+//! participation spread — and annealing stops early once a schedule sits on
+//! every floor.
+//!
+//! For a newcomer mixer, [`Objective::mixer`] adds a coverage term: games
+//! seat people who have not met yet, and the annealing key ranks schedules by
+//! how many different people everyone meets, and how evenly, right after hard
+//! repeats.
+//!
+//! [`by_rounds`] fixes the round count instead — every court full for exactly
+//! `R` rounds — and [`by_session`] derives it from clock time: as many full
+//! rounds as a [`Session`] holds.
+//!
+//! Every mode takes its courts as a [`CourtPlan`]: round `i` of a build fills
+//! at most the courts the plan offers in round `i`.
+//!
+//! The `_from` variants warm-start from an existing schedule: its rounds are
+//! kept (cut at the target) and only the rest is built. The `_in_season`
+//! variants build the next night of a [`Season`]: every game also pays
+//! [`Objective::history`] for the partnerships and oppositions it repeats
+//! from earlier nights and [`Objective::same_gender`] for its pairs' earlier
+//! meetings, and the annealing key counts both.
 
-use crate::model::{Game, Man, Roster, Round, Schedule, Team, Woman};
-use crate::search::{optimize, EMPHASIS_BALANCED};
-use crate::verify::verify;
+use crate::bits::Bits;
+use crate::budget::Budget;
+use crate::construct::construct;
+use crate::model::{CourtPlan, Game, Man, Player, Roster, Round, Schedule, Team, Woman};
+use crate::objective::Objective;
+use crate::parallel::{self, derive_seed};
+use crate::pick::{add_to_level, draw, Level, Sides};
+use crate::quota::{Quota, Quotas};
+use crate::search::{cooled, optimize, optimize_from, optimize_in_season, warm_rounds};
+use crate::season::Season;
+use crate::session::Session;
+use crate::verify::LiveReport;
 use rand::rngs::StdRng;
 use rand::{Rng, SeedableRng};
-use std::collections::HashMap;
-
-// Cost weights, separated in magnitude so the objective is effectively
-// lexicographic: avoid partnership/mixed repeats first, then keep play fair,
-// then minimize same-gender oppositions.
-const W_HARD: f64 = 1_000_000.0;
-const W_FAIR: f64 = 1_000.0;
-const W_SAME: f64 = 1.0;
-
-/// Running counts used to score candidate games.
-struct Ledgers {
-    partner: HashMap<(u16, u16), u32>,
-    mixed: HashMap<(u16, u16), u32>,
-    man: HashMap<(u16, u16), u32>,
-    woman: HashMap<(u16, u16), u32>,
-    played_m: Vec<u32>,
-    played_w: Vec<u32>,
-}
-
-impl Ledgers {
-    fn new(roster: Roster) -> Self {
-        Ledgers {
-            partner: HashMap::new(),
-            mixed: HashMap::new(),
-            man: HashMap::new(),
-            woman: HashMap::new(),
-            played_m: vec![0; roster.men as usize],
-            played_w: vec![0; roster.women as usize],
-        }
-    }
-
-    fn man_key(a: Man, b: Man) -> (u16, u16) {
-        if a.0 <= b.0 { (a.0, b.0) } else { (b.0, a.0) }
-    }
-    fn woman_key(x: Woman, y: Woman) -> (u16, u16) {
-        if x.0 <= y.0 { (x.0, y.0) } else { (y.0, x.0) }
-    }
-
-    fn count(m: &HashMap<(u16, u16), u32>, k: (u16, u16)) -> f64 {
-        m.get(&k).copied().unwrap_or(0) as f64
-    }
-
-    /// Cost of a specific game (orientation fixed): partnerships `(a,x),(b,y)`,
-    /// mixed opps `(a,y),(b,x)`.
-    fn cost(&self, a: Man, x: Woman, b: Man, y: Woman) -> f64 {
-        let repeats = Self::count(&self.partner, (a.0, x.0))
-            + Self::count(&self.partner, (b.0, y.0))
-            + Self::count(&self.mixed, (a.0, y.0))
-            + Self::count(&self.mixed, (b.0, x.0));
-        let same = Self::count(&self.man, Self::man_key(a, b))
-            + Self::count(&self.woman, Self::woman_key(x, y));
-        let fair = (self.played_m[a.0 as usize]
-            + self.played_m[b.0 as usize]
-            + self.played_w[x.0 as usize]
-            + self.played_w[y.0 as usize]) as f64;
-        W_HARD * repeats + W_FAIR * fair + W_SAME * same
-    }
-
-    /// The cheaper of the two orientations for players `{a,b} × {x,y}`.
-    fn best_game(&self, a: Man, b: Man, x: Woman, y: Woman) -> (f64, Game) {
-        let c1 = self.cost(a, x, b, y);
-        let c2 = self.cost(a, y, b, x);
-        if c1 <= c2 {
-            (c1, Game::new(Team::new(a, x), Team::new(b, y)))
-        } else {
-            (c2, Game::new(Team::new(a, y), Team::new(b, x)))
+
+/// What a game costs one of its players for the play they already have: the
+/// fairness weight per game played. Under `quotas` it is counted from the
+/// player's maximum instead, and games still owed below the minimum count
+/// double.
+fn load(led: &LiveReport, obj: &Objective, quotas: Option<&Quotas>, p: Player) -> f64 {
+    let played = led.played(p);
+    match quotas {
+        None => obj.fairness * played as f64,
+        Some(quotas) => {
+            let q = quotas.of(p);
+            let owed = (q.min as usize).saturating_sub(played);
+            obj.fairness * (played as f64 - q.max as f64 - owed as f64)
         }
     }
+}
 
-    fn commit(&mut self, g: &Game) {
-        for (m, w) in g.partnerships() {
-            *self.partner.entry((m.0, w.0)).or_insert(0) += 1;
+/// Cost of a specific game (orientation fixed): partnerships `(a,x),(b,y)`,
+/// mixed opps `(a,y),(b,x)`. The default weights are separated in magnitude
+/// so the cost is effectively lexicographic: avoid partnership/mixed repeats
+/// first, then keep play fair, then (for a mixer) seat people who have not
+/// met or, in a season, avoid earlier nights' pairings, then minimize
+/// same-gender oppositions.
+#[allow(clippy::too_many_arguments)]
+fn cost(
+    led: &LiveReport,
+    obj: &Objective,
+    quotas: Option<&Quotas>,
+    a: Man,
+    x: Woman,
+    b: Man,
+    y: Woman,
+) -> f64 {
+    let repeats = led.partnered(a, x) + led.partnered(b, y) + led.opposed(a, y) + led.opposed(b, x);
+    let before = led.partnered_before(a, x)
+        + led.partnered_before(b, y)
+        + led.opposed_before(a, y)
+        + led.opposed_before(b, x);
+    let same = led.men_met(a, b) + led.women_met(x, y);
+    let same = same + led.men_met_before(a, b) + led.women_met_before(x, y);
+    let fair: f64 = [Player::M(a), Player::M(b), Player::W(x), Player::W(y)]
+        .into_iter()
+        .map(|p| load(led, obj, quotas, p))
+        .sum();
+    let met = if obj.coverage > 0.0 { met_pairs(led, a, b, x, y) } else { 0 };
+    obj.repeat * repeats as f64
+        + fair
+        + obj.same_gender * same as f64
+        + obj.coverage * met as f64
+        + obj.history * before as f64
+}
+
+/// How many of the six pairs among `a`, `b`, `x`, `y` have already met, in
+/// any role.
+fn met_pairs(led: &LiveReport, a: Man, b: Man, x: Woman, y: Woman) -> usize {
+    let cross = [(a, x), (a, y), (b, x), (b, y)];
+    let mixed = cross.into_iter().filter(|&(m, w)| led.partnered(m, w) + led.opposed(m, w) > 0);
+    mixed.count() + (led.men_met(a, b) > 0) as usize + (led.women_met(x, y) > 0) as usize
+}
+
+/// The cheaper of the two orientations for players `{a,b} × {x,y}`.
+#[allow(clippy::too_many_arguments)]
+fn best_game(
+    led: &LiveReport,
+    obj: &Objective,
+    quotas: Option<&Quotas>,
+    a: Man,
+    b: Man,
+    x: Woman,
+    y: Woman,
+) -> (f64, Game) {
+    let c1 = cost(led, obj, quotas, a, x, b, y);
+    let c2 = cost(led, obj, quotas, a, y, b, x);
+    if c1 <= c2 {
+        (c1, Game::new(Team::new(a, x), Team::new(b, y)))
+    } else {
+        (c2, Game::new(Team::new(a, y), Team::new(b, x)))
+    }
+}
+
+/// Every pair from `players` with its side cost, cheapest first.
+fn sides<P: Copy>(players: &[P], cost: impl Fn(P, P) -> f64) -> Vec<(f64, P, P)> {
+    let mut out: Vec<(f64, P, P)> = Vec::new();
+    for (i, &a) in players.iter().enumerate() {
+        for &b in &players[i + 1..] {
+            out.push((cost(a, b), a, b));
         }
-        for (m, w) in g.mixed_opps() {
-            *self.mixed.entry((m.0, w.0)).or_insert(0) += 1;
+    }
+    out.sort_by(|p, q| p.0.total_cmp(&q.0));
+    out
+}
+
+/// A game at the best cost so far: every repeat-free game of a man pair at a
+/// women level (counted, not listed), or one game with repeats.
+#[derive(Clone, Copy)]
+enum Hit {
+    Fresh(Man, Man, usize),
+    Game(Game),
+}
+
+/// The hits at the least cost offered so far, weighted by how many games each
+/// stands for.
+struct Ties {
+    best: f64,
+    hits: Vec<(usize, Hit)>,
+}
+
+impl Ties {
+    fn offer(&mut self, c: f64, n: usize, hit: Hit) {
+        if c < self.best - 1e-9 {
+            self.best = c;
+            self.hits.clear();
+            self.hits.push((n, hit));
+        } else if (c - self.best).abs() <= 1e-9 {
+            self.hits.push((n, hit));
         }
-        *self.man.entry(Self::man_key(g.a.man, g.b.man)).or_insert(0) += 1;
-        *self.woman.entry(Self::woman_key(g.a.woman, g.b.woman)).or_insert(0) += 1;
-        self.played_m[g.a.man.0 as usize] += 1;
-        self.played_m[g.b.man.0 as usize] += 1;
-        self.played_w[g.a.woman.0 as usize] += 1;
-        self.played_w[g.b.woman.0 as usize] += 1;
     }
 }
 
-/// Greedily build rounds until `total_games` games are placed. Each round holds
-/// up to `courts` player-disjoint games; each game is the minimum-cost choice
-/// (ties broken at random), so repeats stay at their floor and byes fall on
-/// whoever has played least.
-fn build(roster: Roster, courts: u16, total_games: usize, rng: &mut StdRng) -> Vec<Vec<Game>> {
-    let mut led = Ledgers::new(roster);
-    let mut rounds: Vec<Vec<Game>> = Vec::new();
-    let mut placed = 0usize;
+/// Candidate scanner for one Part 2 round. A game's fairness and same-gender
+/// cost splits into a men's side and a women's side, both fixed for the free
+/// players while the round fills, so they are priced once per round: the men
+/// pairs sorted, the women pairs grouped into [`Level`]s of equal cost.
+/// Players who have reached their quota's maximum sit the round out. With a
+/// [coverage](Objective::coverage) weight, "fresh" games are those whose four
+/// players have never met across the net or as partners, and the rest are
+/// priced one by one, like games with repeats. In a season, a fresh game
+/// also repeats nothing from the earlier nights.
+struct Scan<'a> {
+    quotas: Option<&'a Quotas>,
+    free_men: Bits,
+    free_women: Bits,
+    side_m: Vec<(f64, Man, Man)>,
+    /// Distinct women-side costs, ascending, and the pairs at each.
+    costs: Vec<f64>,
+    levels: Vec<Level>,
+    sides: Sides,
+    /// Whether fresh means no one has met (see above) rather than no repeat.
+    coverage: bool,
+}
 
-    while placed < total_games {
-        let mut used_m = vec![false; roster.men as usize];
-        let mut used_w = vec![false; roster.women as usize];
-        let mut round: Vec<Game> = Vec::new();
+impl<'a> Scan<'a> {
+    fn new(led: &LiveReport, roster: Roster, obj: &Objective, quotas: Option<&'a Quotas>) -> Self {
+        let w = roster.women as usize;
+        let open = |p: Player| quotas.is_none_or(|q| led.played(p) < q.of(p).max as usize);
+        let men: Vec<Man> = roster.men_iter().filter(|&m| open(Player::M(m))).collect();
+        let women: Vec<Woman> = roster.women_iter().filter(|&x| open(Player::W(x))).collect();
+        let load = |p: Player| load(led, obj, quotas, p);
+        // Same-gender meetings, and whether the pair has met at all.
+        let met = |n: usize| obj.same_gender * n as f64 + obj.coverage * (n > 0) as u8 as f64;
+        let before = |n: usize| obj.same_gender * n as f64;
+        let side_m = sides(&men, |a, b| {
+            let met = met(led.men_met(a, b)) + before(led.men_met_before(a, b));
+            load(Player::M(a)) + load(Player::M(b)) + met
+        });
+        let side_w = sides(&women, |x, y| {
+            let met = met(led.women_met(x, y)) + before(led.women_met_before(x, y));
+            load(Player::W(x)) + load(Player::W(y)) + met
+        });
+        let mut costs: Vec<f64> = Vec::new();
+        let mut levels: Vec<Level> = Vec::new();
+        for (c, x, y) in side_w {
+            if costs.last() != Some(&c) {
+                costs.push(c);
+            }
+            add_to_level(&mut levels, w, costs.len() - 1, x.0 as usize, y.0 as usize);
+        }
+        let mut free_men = Bits::new(roster.men as usize);
+        let mut free_women = Bits::new(w);
+        men.iter().for_each(|m| free_men.insert(m.0 as usize));
+        women.iter().for_each(|x| free_women.insert(x.0 as usize));
+        let coverage = obj.coverage > 0.0;
+        Scan { quotas, free_men, free_women, side_m, costs, levels, sides: Sides::new(w), coverage }
+    }
+
+    /// Mark `g`'s players as used this round.
+    fn take(&mut self, g: &Game) {
+        self.free_men.remove(g.a.man.0 as usize);
+        self.free_men.remove(g.b.man.0 as usize);
+        self.free_women.remove(g.a.woman.0 as usize);
+        self.free_women.remove(g.b.woman.0 as usize);
+    }
 
-        while round.len() < courts as usize && placed < total_games {
-            let men: Vec<Man> = (0..roster.men).filter(|m| !used_m[*m as usize]).map(Man).collect();
-            let women: Vec<Woman> =
-                (0..roster.women).filter(|w| !used_w[*w as usize]).map(Woman).collect();
-            if men.len() < 2 || women.len() < 2 {
+    /// A uniformly random minimum-cost game among the free players, or `None`
+    /// if fewer than two of either gender are free. The repeat part of a cost
+    /// is never negative, so scanning each side cheapest first stops as soon
+    /// as the sides alone cost more than the best game so far; games with
+    /// repeats are only priced one by one when they could still tie.
+    fn pick(&mut self, led: &LiveReport, obj: &Objective, rng: &mut StdRng) -> Option<Game> {
+        let &cheapest_w = self.costs.first()?;
+        // The least a game that is not fresh costs beyond its sides.
+        let stale = if self.coverage { obj.coverage } else { obj.repeat };
+        let stale = if led.season().is_some() { stale.min(obj.history) } else { stale };
+        let mut ties = Ties { best: f64::INFINITY, hits: Vec::new() };
+        for &(pm, a, b) in &self.side_m {
+            if !self.free_men.contains(a.0 as usize) || !self.free_men.contains(b.0 as usize) {
+                continue;
+            }
+            if pm + cheapest_w > ties.best + 1e-9 {
                 break;
             }
-
-            let mut best_cost = f64::INFINITY;
-            let mut best: Option<Game> = None;
-            let mut ties = 0u32;
-            for i in 0..men.len() {
-                for j in (i + 1)..men.len() {
-                    for k in 0..women.len() {
-                        for l in (k + 1)..women.len() {
-                            let (c, g) = led.best_game(men[i], men[j], women[k], women[l]);
-                            if c < best_cost - 1e-9 {
-                                best_cost = c;
-                                best = Some(g);
-                                ties = 1;
-                            } else if (c - best_cost).abs() <= 1e-9 {
-                                ties += 1;
-                                if rng.random_range(0..ties) == 0 {
-                                    best = Some(g);
-                                }
-                            }
+            load_sides(&mut self.sides, self.coverage, led, a, b, &self.free_women);
+            for (l, &pw) in self.costs.iter().enumerate() {
+                if pm + pw > ties.best + 1e-9 {
+                    break;
+                }
+                let n = self.sides.count(&self.levels[l]);
+                if n > 0 {
+                    ties.offer(pm + pw, n, Hit::Fresh(a, b, l));
+                }
+                if pm + pw + stale > ties.best + 1e-9 {
+                    continue;
+                }
+                for x in self.free_women.iter() {
+                    for y in self.levels[l][x].iter().filter(|&y| y > x) {
+                        if self.free_women.contains(y) && !self.sides.fresh(x, y) {
+                            let (c, g) = best_game(led, obj, self.quotas, a, b, Woman(x as u16), Woman(y as u16));
+                            ties.offer(c, 1, Hit::Game(g));
                         }
                     }
                 }
             }
+        }
+        match draw(ties.hits, rng)? {
+            (r, Hit::Fresh(a, b, l)) => {
+                load_sides(&mut self.sides, self.coverage, led, a, b, &self.free_women);
+                Some(self.sides.nth(&self.levels[l], a, b, r))
+            }
+            (_, Hit::Game(g)) => Some(g),
+        }
+    }
+}
+
+/// Load the fresh women sides for men `a` and `b` (see [`Scan`]).
+fn load_sides(sides: &mut Sides, coverage: bool, led: &LiveReport, a: Man, b: Man, free: &Bits) {
+    if coverage {
+        sides.load_unmet(led, a, b, free);
+    } else {
+        sides.load(led, a, b, free);
+    }
+    sides.unplayed_before(led, a, b);
+}
+
+/// Greedily build rounds after the `warm` ones (cut off once `total_games` games
+/// are in) until `total_games` games are placed. Round `i` holds
+/// up to `courts.at(i)` player-disjoint games; each game is the minimum-cost choice
+/// (ties broken at random), so repeats stay at their floor and byes fall on
+/// whoever has played least (or, under `quotas`, is furthest from their
+/// quota). Starts from the ledger `night`, empty or carrying a season's
+/// history; returns the rounds and their live report.
+fn build(
+    night: &LiveReport,
+    courts: &CourtPlan,
+    total_games: usize,
+    obj: &Objective,
+    quotas: Option<&Quotas>,
+    warm: &[Vec<Game>],
+    rng: &mut StdRng,
+) -> (Vec<Vec<Game>>, LiveReport) {
+    let mut led = night.clone();
+    let mut rounds: Vec<Vec<Game>> = Vec::new();
+    let mut placed = 0usize;
+    for round in warm {
+        if placed == total_games {
+            break;
+        }
+        let kept = &round[..round.len().min(total_games - placed)];
+        led.add_round(kept);
+        rounds.push(kept.to_vec());
+        placed += kept.len();
+    }
+    fill(&mut led, &mut rounds, night.roster(), courts, total_games, obj, quotas, rng);
+    (rounds, led)
+}
+
+/// Append greedily built rounds to `rounds` (already in `led`) until
+/// `total_games` games are placed or no game can be formed.
+#[allow(clippy::too_many_arguments)]
+fn fill(
+    led: &mut LiveReport,
+    rounds: &mut Vec<Vec<Game>>,
+    roster: Roster,
+    courts: &CourtPlan,
+    total_games: usize,
+    obj: &Objective,
+    quotas: Option<&Quotas>,
+    rng: &mut StdRng,
+) {
+    let mut placed = led.games();
+    while placed < total_games {
+        let mut round: Vec<Game> = Vec::new();
+        led.new_round();
+        let mut scan = Scan::new(led, roster, obj, quotas);
 
-            match best {
+        let cap = courts.at(rounds.len()) as usize;
+        while round.len() < cap && placed < total_games {
+            match scan.pick(led, obj, rng) {
                 Some(g) => {
-                    led.commit(&g);
-                    used_m[g.a.man.0 as usize] = true;
-                    used_m[g.b.man.0 as usize] = true;
-                    used_w[g.a.woman.0 as usize] = true;
-                    used_w[g.b.woman.0 as usize] = true;
+                    led.add_game(&g);
+                    scan.take(&g);
                     round.push(g);
                     placed += 1;
                 }
@@ -165,35 +364,223 @@ fn build(roster: Roster, courts: u16, total_games: usize, rng: &mut StdRng) -> V
         }
 
         if round.is_empty() {
+            led.remove_round(&round);
             break; // roster too small to place any game
         }
         rounds.push(round);
     }
-
-    rounds
 }
 
 /// Comparable quality key for a Part 2 schedule: fewest partnership+mixed
-/// repeats, then fewest same-gender repeats, then most balanced participation.
-fn score(sched: &Schedule, roster: Roster, courts: u16) -> (usize, usize, usize) {
-    let r = verify(sched, roster, courts);
+/// repeats, then — for a mixer — most people met and then the evenest
+/// spread of them, then — in a season — fewest partnerships and mixed
+/// oppositions from earlier nights, then fewest same-gender repeats (earlier
+/// nights' meetings included), then most balanced participation (under
+/// `quotas`: fewest games missed or overplayed against them).
+type Key = (usize, usize, usize, usize, usize, usize);
+
+fn score(led: &LiveReport, roster: Roster, obj: &Objective, quotas: Option<&Quotas>) -> Key {
+    let fairness = match quotas {
+        None => led.participation_spread(),
+        Some(q) => {
+            let players = roster.men_iter().map(Player::M).chain(roster.women_iter().map(Player::W));
+            players.map(|p| q.of(p).miss(led.played(p))).sum()
+        }
+    };
+    let (unmet, met_spread) = if obj.coverage > 0.0 { coverage(led, roster) } else { (0, 0) };
     (
-        r.partner_repeat_excess + r.mixed_repeat_excess,
-        r.man_repeat_excess + r.woman_repeat_excess,
-        r.participation_spread(),
+        led.partner_repeat_excess() + led.mixed_repeat_excess(),
+        unmet,
+        met_spread,
+        led.season_repeats(),
+        led.man_repeat_excess() + led.woman_repeat_excess() + led.season_meetings(),
+        fairness,
     )
 }
 
-/// Build a `total_games`-game schedule, best of several randomized restarts.
-fn build_best(roster: Roster, courts: u16, total_games: usize, seed: u64) -> Schedule {
-    let mut rng = StdRng::seed_from_u64(seed);
-    let mut best: Option<((usize, usize, usize), Schedule)> = None;
-    for _ in 0..60 {
-        let rounds = build(roster, courts, total_games, &mut rng);
-        let sched = Schedule::new(rounds.into_iter().map(Round::new).collect());
-        let key = score(&sched, roster, courts);
-        if best.as_ref().map_or(true, |(bk, _)| key < *bk) {
-            best = Some((key, sched));
+/// The mixer terms of a [`Key`]: the people players could have met in their
+/// games (three a game, up to everyone else) but did not, summed, and the
+/// spread of the people they did meet.
+fn coverage(led: &LiveReport, roster: Roster) -> (usize, usize) {
+    let others = roster.total_players().saturating_sub(1);
+    let players = roster.men_iter().map(Player::M).chain(roster.women_iter().map(Player::W));
+    let (mut unmet, mut lo, mut hi) = (0, usize::MAX, 0);
+    for p in players {
+        let met = led.people_met(p);
+        unmet += (3 * led.played(p)).min(others).saturating_sub(met);
+        (lo, hi) = (lo.min(met), hi.max(met));
+    }
+    (unmet, hi.saturating_sub(lo))
+}
+
+/// A [`Key`] no schedule of `games` games can beat: the repeat floors and,
+/// without quotas, the participation spread forced by splitting each gender's
+/// `2·games` seats among its players (quota misses, the mixer terms and the
+/// season's repeats have floor zero).
+fn floor(roster: Roster, games: usize, quotas: Option<&Quotas>) -> Key {
+    let split = |n: u16| {
+        let n = (n as usize).max(1);
+        ((2 * games) / n, (2 * games).div_ceil(n))
+    };
+    let ((lo_m, hi_m), (lo_w, hi_w)) = (split(roster.men), split(roster.women));
+    let spread = if quotas.is_some() { 0 } else { hi_m.max(hi_w) - lo_m.min(lo_w) };
+    (
+        roster.min_partner_repeats(games) + roster.min_mixed_repeats(games),
+        0,
+        0,
+        0,
+        roster.min_man_repeats(games) + roster.min_woman_repeats(games),
+        spread,
+    )
+}
+
+/// Greedy restarts per Part 2 build.
+const RESTARTS: usize = 60;
+/// Annealing runs per Part 2 build, one from each of the best restarts.
+const ANNEAL_STARTS: usize = 4;
+/// Ruin-and-recreate steps per annealing run.
+const ANNEAL_STEPS: u32 = 600;
+/// Starting temperature, in units of the first [`Key`] term that differs.
+const T_START: f64 = 1.5;
+
+/// Whether to move from a schedule scored `cur` to one scored `trial`: always
+/// if no worse, else with the Metropolis chance of the first term that got
+/// worse — the key stays lexicographic, only the step size is annealed.
+fn accept(trial: Key, cur: Key, t: f64, rng: &mut StdRng) -> bool {
+    let terms = [
+        (trial.0, cur.0),
+        (trial.1, cur.1),
+        (trial.2, cur.2),
+        (trial.3, cur.3),
+        (trial.4, cur.4),
+        (trial.5, cur.5),
+    ];
+    match terms.into_iter().find(|(a, b)| a != b) {
+        Some((a, b)) if a > b => rng.random::<f64>() < (-((a - b) as f64) / t).exp(),
+        _ => true,
+    }
+}
+
+/// Polish a greedy build with the Part 1 move set ([`search`](crate::search)):
+/// rip out one or two whole rounds and rebuild them greedily, annealing with
+/// reheats over the [`Key`], never adding a round. The first `kept` rounds
+/// (the warm start's) are never ripped out. Rounds moving up into a slot
+/// with fewer courts shed their extra games for the rebuild to place again.
+/// Stops early at the [`floor`]. Returns the best schedule seen and its key.
+#[allow(clippy::too_many_arguments)]
+fn anneal(
+    roster: Roster,
+    courts: &CourtPlan,
+    total_games: usize,
+    obj: &Objective,
+    quotas: Option<&Quotas>,
+    kept: usize,
+    (mut cur, mut led): (Vec<Vec<Game>>, LiveReport),
+    rng: &mut StdRng,
+) -> (Key, Vec<Vec<Game>>) {
+    let mut key = score(&led, roster, obj, quotas);
+    let (mut best, mut best_led, mut best_key) = (cur.clone(), led.clone(), key);
+    let floor = floor(roster, led.games(), quotas);
+    let span = (ANNEAL_STEPS / 4).max(1);
+    let (mut pos, mut since_improve) = (0u32, 0u32);
+    for _ in 0..ANNEAL_STEPS {
+        if cur.len() <= kept || best_key <= floor {
+            break;
+        }
+        let t = cooled(T_START, pos, span);
+        // The step edits `cur` and `led` in place; these undo a rejection.
+        let (games, len) = (led.games(), cur.len());
+        let mut dropped: Vec<(usize, Vec<Game>)> = Vec::new();
+        let mut trimmed: Vec<(usize, Vec<Game>)> = Vec::new();
+        for _ in 0..rng.random_range(1..=2usize).min(cur.len() - kept) {
+            let idx = rng.random_range(kept..cur.len());
+            let round = cur.remove(idx);
+            led.remove_round(&round);
+            dropped.push((idx, round));
+        }
+        for (i, round) in cur.iter_mut().enumerate().skip(kept) {
+            let cap = courts.at(i) as usize;
+            if round.len() > cap {
+                let shed = round.split_off(cap);
+                shed.iter().for_each(|g| led.remove_game(g));
+                trimmed.push((i, shed));
+            }
+        }
+        let rebuilt = cur.len();
+        fill(&mut led, &mut cur, roster, courts, total_games, obj, quotas, rng);
+        let trial_key = score(&led, roster, obj, quotas);
+        // A rebuild that cannot place every game again, or needs more rounds
+        // to, is never taken.
+        if led.games() == games && cur.len() <= len && accept(trial_key, key, t, rng) {
+            key = trial_key;
+        } else {
+            for round in cur.drain(rebuilt..).rev() {
+                led.remove_round(&round);
+            }
+            for (i, shed) in trimmed {
+                shed.iter().for_each(|g| led.add_game(g));
+                cur[i].extend(shed);
+            }
+            for (idx, round) in dropped.into_iter().rev() {
+                led.add_round(&round);
+                cur.insert(idx, round);
+            }
+        }
+        pos += 1;
+        if key < best_key {
+            (best, best_led, best_key) = (cur.clone(), led.clone(), key);
+            since_improve = 0;
+        } else {
+            since_improve += 1;
+        }
+        if since_improve >= span {
+            (cur, led, key) = (best.clone(), best_led.clone(), best_key);
+            pos = 0;
+            since_improve = 0;
+        }
+    }
+    (best_key, best)
+}
+
+/// Build a `total_games`-game schedule after the `warm` rounds from the
+/// ledger `night`: several randomized greedy restarts, then an annealing run
+/// from each of the best few (all in parallel, one derived seed each).
+fn build_best(
+    night: &LiveReport,
+    courts: &CourtPlan,
+    total_games: usize,
+    obj: &Objective,
+    quotas: Option<&Quotas>,
+    warm: &[Vec<Game>],
+    seed: u64,
+) -> Schedule {
+    let roster = night.roster();
+    let mut restarts = parallel::run_indexed(RESTARTS, |i| {
+        let mut rng = StdRng::seed_from_u64(derive_seed(seed, i));
+        let (rounds, led) = build(night, courts, total_games, obj, quotas, warm, &mut rng);
+        (score(&led, roster, obj, quotas), rounds, led)
+    });
+    restarts.sort_by_key(|r| r.0);
+    restarts.truncate(ANNEAL_STARTS);
+    // The warm rounds `build` keeps: those begun before the target was met.
+    let mut placed = 0;
+    let kept = warm
+        .iter()
+        .take_while(|r| {
+            let open = placed < total_games;
+            placed += r.len();
+            open
+        })
+        .count();
+    let runs = parallel::run_indexed(restarts.len(), |i| {
+        let mut rng = StdRng::seed_from_u64(derive_seed(seed, RESTARTS + i));
+        let (_, rounds, led) = restarts[i].clone();
+        anneal(roster, courts, total_games, obj, quotas, kept, (rounds, led), &mut rng)
+    });
+    let mut best: Option<(Key, Schedule)> = None;
+    for (key, rounds) in runs {
+        if best.as_ref().is_none_or(|(bk, _)| key < *bk) {
+            best = Some((key, Schedule::new(rounds.into_iter().map(Round::new).collect())));
         }
     }
     best.map(|(_, s)| s).unwrap_or_default()
@@ -206,24 +593,322 @@ fn games_for_per_player(roster: Roster, n_each: u32) -> usize {
     (n_each as usize * players + 2) / 4
 }
 
+/// Games in which every man plays `men_each` and every woman `women_each`:
+/// each game seats two of each, so `M·men_each = W·women_each = 2G`. Errors
+/// with the arithmetic when no such `G` exists.
+pub fn games_for_per_gender(roster: Roster, men_each: u32, women_each: u32) -> Result<usize, String> {
+    let men_seats = roster.men as usize * men_each as usize;
+    let women_seats = roster.women as usize * women_each as usize;
+    if men_seats != women_seats {
+        return Err(format!(
+            "{} men playing {} is {} seats but {} women playing {} is {}; every game seats two of each",
+            roster.men, men_each, men_seats, roster.women, women_each, women_seats
+        ));
+    }
+    if !men_seats.is_multiple_of(2) {
+        return Err(format!("{} seats a gender cannot be split into games of two", men_seats));
+    }
+    Ok(men_seats / 2)
+}
+
+/// The balanced full round-robin, which is exactly Part 1.
+fn is_full_round_robin(roster: Roster, courts: &CourtPlan, n_each: u32) -> bool {
+    let n = roster.men as usize;
+    let balanced_even = roster.women as usize == n && n >= 2 && n.is_multiple_of(2);
+    balanced_even && n_each as usize == n && courts.fewest() as usize >= n / 2
+}
+
+/// Part 1 for a target that is the balanced full round-robin: [`optimize`],
+/// or [`optimize_in_season`] when `night` is a night of a season.
+fn round_robin(night: &LiveReport, courts: &CourtPlan, objective: &Objective, seed: u64) -> Schedule {
+    match night.season() {
+        Some(season) => {
+            let unlimited = Budget::unlimited();
+            optimize_in_season(season, courts, Some(20_000), &unlimited, objective, seed, &|_| {})
+        }
+        None => optimize(night.roster(), courts, 20_000, objective, seed),
+    }
+}
+
 /// Part 2 mode: each player plays about `n_each` games.
 ///
-/// For the balanced full round-robin (`n_each == n`, courts ≥ n/2) this is
+/// For the balanced full round-robin (`n_each == n`, courts ≥ n/2 every round) this is
 /// exactly Part 1, so it delegates to [`optimize`] to inherit the optimal
 /// construction. Otherwise it targets `round(n_each · players / 4)` games with
-/// fair byes.
-pub fn by_games_per_player(roster: Roster, courts: u16, n_each: u32, seed: u64) -> Schedule {
-    let n = roster.men as usize;
-    let balanced_even = roster.women as usize == n && n >= 2 && n % 2 == 0;
-    if balanced_even && n_each as usize == n && courts as usize >= n / 2 {
-        return optimize(roster, courts, 20_000, EMPHASIS_BALANCED, seed);
+/// fair byes. `objective` weighs repeats, fairness and same-gender meetings.
+pub fn by_games_per_player(
+    roster: Roster,
+    courts: impl Into<CourtPlan>,
+    n_each: u32,
+    objective: &Objective,
+    seed: u64,
+) -> Schedule {
+    let courts = &courts.into();
+    per_player(&LiveReport::new(roster, courts), courts, n_each, objective, seed)
+}
+
+/// [`by_games_per_player`] for the next night of `season`, on its roster.
+pub fn by_games_per_player_in_season(
+    season: &Season,
+    courts: impl Into<CourtPlan>,
+    n_each: u32,
+    objective: &Objective,
+    seed: u64,
+) -> Schedule {
+    let courts = &courts.into();
+    per_player(&LiveReport::in_season(season, courts), courts, n_each, objective, seed)
+}
+
+/// [`by_games_per_player`] from the ledger `night`.
+fn per_player(
+    night: &LiveReport,
+    courts: &CourtPlan,
+    n_each: u32,
+    obj: &Objective,
+    seed: u64,
+) -> Schedule {
+    let roster = night.roster();
+    if is_full_round_robin(roster, courts, n_each) {
+        return round_robin(night, courts, obj, seed);
     }
     let total = games_for_per_player(roster, n_each);
-    build_best(roster, courts, total, seed)
+    build_best(night, courts, total, obj, None, &[], seed)
+}
+
+/// Part 2 mode: every man plays exactly `men_each` games and every woman
+/// exactly `women_each` — the exact version of [`by_games_per_player`] for
+/// `M ≠ W`, where one `N` for everyone is only an average.
+///
+/// The game count is [`games_for_per_gender`]; it errors when the two
+/// targets don't agree on one. The counts are held as exact [`Quotas`], so
+/// nobody plays past their target and byes go to whoever is closest to it.
+pub fn by_games_per_gender(
+    roster: Roster,
+    courts: impl Into<CourtPlan>,
+    men_each: u32,
+    women_each: u32,
+    objective: &Objective,
+    seed: u64,
+) -> Result<Schedule, String> {
+    let courts = &courts.into();
+    let night = LiveReport::new(roster, courts);
+    per_gender(&night, courts, (men_each, women_each), objective, seed)
+}
+
+/// [`by_games_per_gender`] for the next night of `season`, on its roster.
+pub fn by_games_per_gender_in_season(
+    season: &Season,
+    courts: impl Into<CourtPlan>,
+    men_each: u32,
+    women_each: u32,
+    objective: &Objective,
+    seed: u64,
+) -> Result<Schedule, String> {
+    let courts = &courts.into();
+    let night = LiveReport::in_season(season, courts);
+    per_gender(&night, courts, (men_each, women_each), objective, seed)
+}
+
+/// [`by_games_per_gender`] from the ledger `night`.
+fn per_gender(
+    night: &LiveReport,
+    courts: &CourtPlan,
+    (men_each, women_each): (u32, u32),
+    obj: &Objective,
+    seed: u64,
+) -> Result<Schedule, String> {
+    let roster = night.roster();
+    let total = games_for_per_gender(roster, men_each, women_each)?;
+    if men_each == women_each && is_full_round_robin(roster, courts, men_each) {
+        return Ok(round_robin(night, courts, obj, seed));
+    }
+    let quotas = Quotas::by_gender(roster, Quota::exactly(men_each), Quota::exactly(women_each));
+    Ok(build_best(night, courts, total, obj, Some(&quotas), &[], seed))
+}
+
+/// [`by_games_per_player`] warm-started from `initial`, which must be
+/// structurally valid for this roster and court count (repeats are fine). In
+/// the full round-robin case this is [`optimize_from`], which needs `initial`
+/// legal outright.
+pub fn by_games_per_player_from(
+    initial: &Schedule,
+    roster: Roster,
+    courts: impl Into<CourtPlan>,
+    n_each: u32,
+    objective: &Objective,
+    seed: u64,
+) -> Result<Schedule, String> {
+    let courts = &courts.into();
+    if is_full_round_robin(roster, courts, n_each) {
+        let unlimited = Budget::unlimited();
+        return optimize_from(initial, roster, courts, Some(20_000), &unlimited, objective, seed);
+    }
+    let warm = warm_rounds(initial, roster, courts, false)?;
+    let total = games_for_per_player(roster, n_each);
+    let night = LiveReport::new(roster, courts);
+    Ok(build_best(&night, courts, total, objective, None, &warm, seed))
 }
 
 /// Part 2 mode: cap the schedule at exactly `total_games` games (the final
 /// round may be partial to hit the count exactly).
-pub fn by_total_games(roster: Roster, courts: u16, total_games: usize, seed: u64) -> Schedule {
-    build_best(roster, courts, total_games, seed)
+pub fn by_total_games(
+    roster: Roster,
+    courts: impl Into<CourtPlan>,
+    total_games: usize,
+    objective: &Objective,
+    seed: u64,
+) -> Schedule {
+    let courts = &courts.into();
+    let night = LiveReport::new(roster, courts);
+    build_best(&night, courts, total_games, objective, None, &[], seed)
+}
+
+/// [`by_total_games`] for the next night of `season`, on its roster.
+pub fn by_total_games_in_season(
+    season: &Season,
+    courts: impl Into<CourtPlan>,
+    total_games: usize,
+    objective: &Objective,
+    seed: u64,
+) -> Schedule {
+    let courts = &courts.into();
+    let night = LiveReport::in_season(season, courts);
+    build_best(&night, courts, total_games, objective, None, &[], seed)
+}
+
+/// [`by_total_games`] warm-started from `initial`: its rounds are kept in
+/// order up to `total_games` games and the rest are built around them.
+/// `initial` must be structurally valid for this roster and court count.
+pub fn by_total_games_from(
+    initial: &Schedule,
+    roster: Roster,
+    courts: impl Into<CourtPlan>,
+    total_games: usize,
+    objective: &Objective,
+    seed: u64,
+) -> Result<Schedule, String> {
+    let courts = &courts.into();
+    let warm = warm_rounds(initial, roster, courts, false)?;
+    let night = LiveReport::new(roster, courts);
+    Ok(build_best(&night, courts, total_games, objective, None, &warm, seed))
+}
+
+/// Part 2 mode: exactly `rounds` rounds with every court busy — the games
+/// the plan's first `rounds` rounds hold ([`CourtPlan::games`]), byes rotated
+/// fairly.
+///
+/// When that is the whole balanced round-robin (an even `n × n` roster, `n`
+/// rounds of `n/2` games) this is exactly Part 1, so it delegates to
+/// [`optimize`] to inherit the optimal construction. Where Part 1 would
+/// rather spread the games over more rounds (no optimal construction for
+/// that `n`), the best construction that fits the `n` rounds is annealed
+/// for the same-gender terms instead.
+pub fn by_rounds(
+    roster: Roster,
+    courts: impl Into<CourtPlan>,
+    rounds: usize,
+    objective: &Objective,
+    seed: u64,
+) -> Schedule {
+    let courts = &courts.into();
+    full_rounds(&LiveReport::new(roster, courts), courts, rounds, objective, seed)
+}
+
+/// [`by_rounds`] for the next night of `season`, on its roster.
+pub fn by_rounds_in_season(
+    season: &Season,
+    courts: impl Into<CourtPlan>,
+    rounds: usize,
+    objective: &Objective,
+    seed: u64,
+) -> Schedule {
+    let courts = &courts.into();
+    full_rounds(&LiveReport::in_season(season, courts), courts, rounds, objective, seed)
+}
+
+/// [`by_rounds`] from the ledger `night`, empty or carrying a season's
+/// history. The construction fallback only applies to an empty one: it
+/// knows nothing of earlier nights.
+fn full_rounds(
+    night: &LiveReport,
+    courts: &CourtPlan,
+    rounds: usize,
+    obj: &Objective,
+    seed: u64,
+) -> Schedule {
+    let roster = night.roster();
+    let total = courts.games(roster, rounds);
+    let balanced_even =
+        roster.men == roster.women && roster.men >= 2 && roster.men.is_multiple_of(2);
+    let fits = courts.fewest() as usize >= roster.men as usize / 2;
+    if balanced_even && rounds > 0 && total == roster.max_games() {
+        let best = round_robin(night, courts, obj, seed);
+        if best.rounds.len() == rounds {
+            return best;
+        }
+        if let Some(fit) = construct(roster).filter(|_| fits && night.season().is_none()) {
+            let led = LiveReport::of(&fit, roster, courts);
+            let cur: Vec<Vec<Game>> = fit.rounds.into_iter().map(|r| r.games).collect();
+            let runs = parallel::run_indexed(ANNEAL_STARTS, |i| {
+                let mut rng = StdRng::seed_from_u64(derive_seed(seed, i));
+                let start = (cur.clone(), led.clone());
+                anneal(roster, courts, total, obj, None, 0, start, &mut rng)
+            });
+            let (_, best) = runs.into_iter().min_by_key(|r| r.0).expect("ANNEAL_STARTS > 0");
+            return Schedule::new(best.into_iter().map(Round::new).collect());
+        }
+    }
+    build_best(night, courts, total, obj, None, &[], seed)
+}
+
+/// Part 2 mode: fill the rounds that fit `session`, every court busy — that
+/// is [`by_rounds`] with [`Session::rounds`] rounds (none if no game fits);
+/// [`Session::round_times`] gives their times.
+pub fn by_session(
+    roster: Roster,
+    courts: impl Into<CourtPlan>,
+    session: &Session,
+    objective: &Objective,
+    seed: u64,
+) -> Schedule {
+    let courts = &courts.into();
+    by_rounds(roster, courts, session.rounds(), objective, seed)
+}
+
+/// [`by_session`] for the next night of `season`, on its roster.
+pub fn by_session_in_season(
+    season: &Season,
+    courts: impl Into<CourtPlan>,
+    session: &Session,
+    objective: &Objective,
+    seed: u64,
+) -> Schedule {
+    let courts = &courts.into();
+    by_rounds_in_season(season, courts, session.rounds(), objective, seed)
+}
+
+/// Part 2 mode: each player plays within their quota. The game count is the
+/// most the quotas allow ([`Quotas::total_games`]); byes go to whoever is
+/// closest to their maximum, players owed games below their minimum play
+/// first, and nobody plays past their maximum — a court stays empty instead,
+/// and the schedule may then fall short of the count.
+/// Add `quotas` to [`verify_with`](crate::verify::verify_with) to compare
+/// each player's games against their quota.
+///
+/// Errors if the quotas are for another roster or contradict themselves (see
+/// [`Quotas::total_games`]).
+pub fn by_quotas(
+    roster: Roster,
+    courts: impl Into<CourtPlan>,
+    quotas: &Quotas,
+    objective: &Objective,
+    seed: u64,
+) -> Result<Schedule, String> {
+    let courts = &courts.into();
+    if !quotas.fits(roster) {
+        return Err("the quotas are for a different roster".to_string());
+    }
+    let total = quotas.total_games()?;
+    let night = LiveReport::new(roster, courts);
+    Ok(build_best(&night, courts, total, objective, Some(quotas), &[], seed))
 }
@@ -6,39 +6,180 @@
 //! sanity, and reports the soft same-gender repeat counts against their
 //! information-theoretic floor so we always know how close we are to optimal.
 
-use crate::model::{Game, Player, Roster, Schedule};
-use std::collections::HashMap;
+use crate::bits::Bits;
+use crate::model::{CourtPlan, Game, Man, Player, Roster, Round, Schedule, SideGame, Woman};
+use crate::pack;
+use crate::rules::{self, Rule};
+use crate::season::Season;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::sync::Arc;
+
+/// Where a game sits in a schedule: 0-based round and court (see
+/// [`Round::court`](crate::model::Round::court)). Displayed 1-based, matching
+/// the printed grid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Slot {
+    pub round: usize,
+    pub court: usize,
+}
+
+impl Slot {
+    /// The slot of game `gi` in round `ri`.
+    pub fn of(round: &Round, ri: usize, gi: usize) -> Self {
+        Slot { round: ri, court: round.court(gi) as usize }
+    }
+}
+
+impl fmt::Display for Slot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "round {} court {}", self.round + 1, self.court + 1)
+    }
+}
 
 /// A structural or hard-constraint violation. A schedule with any of these is
 /// illegal; a schedule with none is *legal* (its quality is then measured by
 /// the soft metrics in [`Report`]).
+///
+/// Every variant names the exact [`Slot`]s involved, so a hand edit can go
+/// straight to the cell to fix; [`Display`](fmt::Display) gives the human
+/// explanation.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Violation {
     /// A game has two identical men or two identical women.
-    Malformed { round: usize, game: usize },
+    Malformed { at: Slot },
     /// A player index is outside the roster.
-    OutOfRange { round: usize, game: usize, who: Player },
-    /// A player appears in two games in the same round.
-    DoubleBooked { round: usize, who: Player },
-    /// A `(man, woman)` partnership is used more than once.
-    RepeatedPartnership { man: u16, woman: u16, count: usize },
-    /// A `(man, woman)` mixed opposition occurs more than once.
-    RepeatedMixedOpp { man: u16, woman: u16, count: usize },
+    OutOfRange { at: Slot, who: Player },
+    /// A game is on a court its round does not offer.
+    CourtOutOfRange { at: Slot },
+    /// Two or more games of one round share a court.
+    CourtClash { at: Vec<Slot> },
+    /// A player appears in two or more games of the same round; `at` lists
+    /// every one of those games.
+    DoubleBooked { who: Player, at: Vec<Slot> },
+    /// A side game in `round` takes `who`, who is not free for it: already
+    /// playing, in another side game, or not on the roster. `at` lists the
+    /// side game's court and every game or side game `who` is already in; a
+    /// drill has no court of its own.
+    SideBooked { round: usize, who: Player, at: Vec<Slot> },
+    /// A `(man, woman)` partnership is used more than once, at each of `at`.
+    RepeatedPartnership { man: Man, woman: Woman, at: Vec<Slot> },
+    /// A `(man, woman)` mixed opposition occurs more than once, at each of `at`.
+    RepeatedMixedOpp { man: Man, woman: Woman, at: Vec<Slot> },
+    /// A breach of a non-built-in [`Rule`], named by `rule`.
+    Rule { rule: &'static str, message: String, at: Vec<Slot> },
+}
+
+impl Violation {
+    /// A structural breach (malformed game, unknown player or court, clash,
+    /// double booking) rather than a repeat or a club rule.
+    pub fn is_structural(&self) -> bool {
+        matches!(
+            self,
+            Violation::Malformed { .. }
+                | Violation::OutOfRange { .. }
+                | Violation::CourtOutOfRange { .. }
+                | Violation::CourtClash { .. }
+                | Violation::DoubleBooked { .. }
+                | Violation::SideBooked { .. }
+        )
+    }
+
+    /// Every slot involved, in schedule order.
+    pub fn slots(&self) -> &[Slot] {
+        match self {
+            Violation::Malformed { at }
+            | Violation::OutOfRange { at, .. }
+            | Violation::CourtOutOfRange { at } => {
+                std::slice::from_ref(at)
+            }
+            Violation::DoubleBooked { at, .. }
+            | Violation::SideBooked { at, .. }
+            | Violation::CourtClash { at }
+            | Violation::RepeatedPartnership { at, .. }
+            | Violation::RepeatedMixedOpp { at, .. }
+            | Violation::Rule { at, .. } => at,
+        }
+    }
+}
+
+fn list_slots(at: &[Slot]) -> String {
+    at.iter().map(Slot::to_string).collect::<Vec<_>>().join(", ")
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::Malformed { at } => {
+                write!(f, "{}: a game needs two different men and two different women", at)
+            }
+            Violation::OutOfRange { at, who } => {
+                write!(f, "{}: {} is not on the roster", at, who)
+            }
+            Violation::CourtOutOfRange { at } => write!(f, "{}: there is no such court", at),
+            Violation::CourtClash { at } => {
+                write!(f, "{} games share one court: {}", at.len(), list_slots(at))
+            }
+            Violation::DoubleBooked { who, at } => write!(
+                f,
+                "{} is on {} courts at once: {}",
+                who,
+                at.len(),
+                list_slots(at)
+            ),
+            Violation::SideBooked { round, who, at } if at.is_empty() => {
+                write!(f, "round {}: {} is not free for a side game", round + 1, who)
+            }
+            Violation::SideBooked { who, at, .. } => {
+                write!(f, "{} is not free for a side game: {}", who, list_slots(at))
+            }
+            Violation::RepeatedPartnership { man, woman, at } => write!(
+                f,
+                "{} & {} partner {} times: {}",
+                man,
+                woman,
+                at.len(),
+                list_slots(at)
+            ),
+            Violation::RepeatedMixedOpp { man, woman, at } => write!(
+                f,
+                "{} faces {} {} times: {}",
+                man,
+                woman,
+                at.len(),
+                list_slots(at)
+            ),
+            Violation::Rule { rule, message, at } if at.is_empty() => {
+                write!(f, "[{}] {}", rule, message)
+            }
+            Violation::Rule { rule, message, at } => {
+                write!(f, "[{}] {}: {}", rule, message, list_slots(at))
+            }
+        }
+    }
 }
 
 /// A full quality report for a schedule.
 #[derive(Clone, Debug)]
 pub struct Report {
     pub roster: Roster,
+    /// The most courts any round offers.
     pub courts: u16,
+    /// The courts offered round by round.
+    pub court_plan: CourtPlan,
 
-    /// Hard/structural violations. Empty ⇒ the schedule is legal.
+    /// Hard/structural violations, as emitted by the verifier's rules. Empty ⇒
+    /// the schedule is legal.
     pub violations: Vec<Violation>,
 
     pub games: usize,
     /// The partnership-ledger ceiling, `⌊men·women/2⌋`.
     pub max_games: usize,
     pub rounds: usize,
+    /// The counting floor for `rounds` with these games — court capacity
+    /// (round by round, under a [`CourtPlan`]) and the busiest player (see
+    /// [`pack::lower_bound`]). [`pack::pack_exact`] can prove a higher one.
+    pub round_floor: usize,
 
     /// Extra partnership uses beyond the first, summed. Zero for a Part 1
     /// schedule (hard); positive only when a Part 2 target pushes past the game
@@ -66,10 +207,45 @@ pub struct Report {
     pub games_per_man: Vec<usize>,
     pub games_per_woman: Vec<usize>,
 
+    /// Distinct players each man / woman partners or faces at least once —
+    /// what a social mixer ([`Objective::mixer`](crate::objective::Objective::mixer))
+    /// maximizes.
+    pub met_per_man: Vec<usize>,
+    pub met_per_woman: Vec<usize>,
+
+    /// Games each player has played on each court, `[player][court]`.
+    pub courts_per_man: Vec<Vec<usize>>,
+    pub courts_per_woman: Vec<Vec<usize>>,
+
     /// Byes per round (players idle because courts/opponents ran out).
     pub byes_per_round: Vec<usize>,
-    /// Court-slots used ÷ court-slots offered = `games / (rounds · courts)`.
+    /// Court-slots used ÷ court-slots offered: `games` over the courts the
+    /// plan offers in the schedule's rounds.
     pub court_utilization: f64,
+
+    /// Side games — same-gender doubles and singles for players on a bye
+    /// (see [`side`](crate::side)). They stand outside every ledger above:
+    /// none of the counts, repeats or byes so far include them.
+    pub side_games: usize,
+    /// Side games each man / woman played.
+    pub side_games_per_man: Vec<usize>,
+    pub side_games_per_woman: Vec<usize>,
+    /// Extra meetings beyond the first of any two players in side games,
+    /// summed.
+    pub side_repeat_excess: usize,
+    /// Player-rounds spent in drills.
+    pub drill_slots: usize,
+
+    /// Soft scores emitted by rules, as `(name, value)` in rule order. The
+    /// built-in rules only emit violations; club rules add their scores here.
+    pub rule_scores: Vec<(String, f64)>,
+
+    /// Proven best: no legal schedule has more games, or as many with fewer
+    /// same-gender repeats, and these games fit in no fewer rounds. The
+    /// verifier sets it when the schedule [meets every bound](Self::meets_bounds);
+    /// [`exact::certify`](crate::exact::certify) proves it when the bounds
+    /// cannot be met.
+    pub optimal: bool,
 }
 
 impl Report {
@@ -84,14 +260,7 @@ impl Report {
     /// double-booked within a round. This is the bar for Part 2, where
     /// partnership/mixed repeats are *expected* (soft) rather than forbidden.
     pub fn is_structurally_valid(&self) -> bool {
-        !self.violations.iter().any(|v| {
-            matches!(
-                v,
-                Violation::Malformed { .. }
-                    | Violation::OutOfRange { .. }
-                    | Violation::DoubleBooked { .. }
-            )
-        })
+        !self.violations.iter().any(Violation::is_structural)
     }
 
     /// At the partnership ceiling — the most games this roster can support.
@@ -106,6 +275,27 @@ impl Report {
             && self.woman_repeat_excess == self.woman_repeat_floor
     }
 
+    /// Legal, at the game ceiling and both same-gender floors, in
+    /// `round_floor` rounds: optimal on its face, no search needed.
+    pub fn meets_bounds(&self) -> bool {
+        self.is_legal()
+            && self.hits_game_ceiling()
+            && self.hits_repeat_floor()
+            && self.rounds == self.round_floor
+    }
+
+    /// Worst court rotation: the largest, over players, of (most games on one
+    /// court − fewest on another). `0` or `1` is as even as rotation gets
+    /// (`1` is forced when a player's games don't divide by the court count).
+    pub fn court_imbalance(&self) -> usize {
+        self.courts_per_man
+            .iter()
+            .chain(self.courts_per_woman.iter())
+            .map(|c| c.iter().max().unwrap_or(&0) - c.iter().min().unwrap_or(&0))
+            .max()
+            .unwrap_or(0)
+    }
+
     /// Spread of games across players (max − min). Zero ⇒ perfectly balanced
     /// participation, which matters for the Part 2 per-player target.
     pub fn participation_spread(&self) -> usize {
@@ -114,74 +304,107 @@ impl Report {
         let min = all.copied().min().unwrap_or(0);
         max - min
     }
+
+    /// Most minus fewest distinct people met by any player.
+    pub fn met_spread(&self) -> usize {
+        let all = self.met_per_man.iter().chain(self.met_per_woman.iter());
+        let max = all.clone().copied().max().unwrap_or(0);
+        let min = all.copied().min().unwrap_or(0);
+        max - min
+    }
 }
 
 /// Tally repeats in a histogram: returns `(excess, max_count)` where `excess`
 /// is `Σ max(0, count − 1)` and `max_count` is the largest bucket.
-fn repeat_stats<K>(counts: &HashMap<K, usize>) -> (usize, usize) {
-    let excess = counts.values().map(|&c| c.saturating_sub(1)).sum();
-    let max = counts.values().copied().max().unwrap_or(0);
+fn repeat_stats(counts: impl Iterator<Item = usize> + Clone) -> (usize, usize) {
+    let excess = counts.clone().map(|c| c.saturating_sub(1)).sum();
+    let max = counts.max().unwrap_or(0);
     (excess, max)
 }
 
-/// Verify and score a schedule against a roster and court count.
-pub fn verify(schedule: &Schedule, roster: Roster, courts: u16) -> Report {
-    let mut violations = Vec::new();
+/// Every slot each `(man, woman)` pair occupies in one ledger — `pairs`
+/// picks the ledger ([`Game::partnerships`] or [`Game::mixed_opps`]). Games
+/// with an out-of-range player are skipped, as everywhere in the verifier.
+pub(crate) fn pair_slots(
+    schedule: &Schedule,
+    roster: Roster,
+    pairs: fn(&Game) -> [(Man, Woman); 2],
+) -> BTreeMap<(Man, Woman), Vec<Slot>> {
+    let mut at: BTreeMap<(Man, Woman), Vec<Slot>> = BTreeMap::new();
+    for (ri, round) in schedule.rounds.iter().enumerate() {
+        for (gi, game) in round.games.iter().enumerate() {
+            if in_range(game, roster) {
+                for pair in pairs(game) {
+                    at.entry(pair).or_default().push(Slot::of(round, ri, gi));
+                }
+            }
+        }
+    }
+    at
+}
 
-    // Hard ledgers.
-    let mut partner_counts: HashMap<(u16, u16), usize> = HashMap::new();
-    let mut mixed_counts: HashMap<(u16, u16), usize> = HashMap::new();
+pub(crate) fn on_roster(p: Player, roster: Roster) -> bool {
+    match p {
+        Player::M(m) => m.0 < roster.men,
+        Player::W(w) => w.0 < roster.women,
+    }
+}
+
+pub(crate) fn in_range(g: &Game, roster: Roster) -> bool {
+    g.a.man.0 < roster.men
+        && g.b.man.0 < roster.men
+        && g.a.woman.0 < roster.women
+        && g.b.woman.0 < roster.women
+}
+
+/// Verify and score a schedule against a roster and court count (or a
+/// per-round [`CourtPlan`]), applying the [built-in rules](rules::builtin).
+///
+/// Violations come out in a fixed order: structural ones in schedule order,
+/// then repeated partnerships, then repeated mixed oppositions, each sorted by
+/// `(man, woman)`.
+pub fn verify(schedule: &Schedule, roster: Roster, courts: impl Into<CourtPlan>) -> Report {
+    verify_with(schedule, roster, courts, &rules::builtin())
+}
+
+/// Verify with an explicit rule set: measure the schedule, then let each rule
+/// in turn add its violations and soft scores to the report. Pass a subset of
+/// [`rules::builtin`] to disable a check, or append club rules to enable them.
+pub fn verify_with(
+    schedule: &Schedule,
+    roster: Roster,
+    courts: impl Into<CourtPlan>,
+    rules: &[Box<dyn Rule>],
+) -> Report {
+    let court_plan = courts.into();
+    let courts = court_plan.most();
     let mut man_counts: HashMap<(u16, u16), usize> = HashMap::new();
     let mut woman_counts: HashMap<(u16, u16), usize> = HashMap::new();
 
     // Participation.
     let mut games_per_man = vec![0usize; roster.men as usize];
     let mut games_per_woman = vec![0usize; roster.women as usize];
+    let mut courts_per_man = vec![vec![0usize; courts as usize]; roster.men as usize];
+    let mut courts_per_woman = vec![vec![0usize; courts as usize]; roster.women as usize];
     let mut byes_per_round = Vec::with_capacity(schedule.rounds.len());
 
-    let in_range = |g: &Game| {
-        g.a.man.0 < roster.men
-            && g.b.man.0 < roster.men
-            && g.a.woman.0 < roster.women
-            && g.b.woman.0 < roster.women
-    };
-
-    for (ri, round) in schedule.rounds.iter().enumerate() {
-        let mut seen: HashMap<Player, ()> = HashMap::new();
+    for round in &schedule.rounds {
         let mut active = 0usize;
-
+        // Out-of-range games are the structure rule's to report; skip them
+        // here so the ledgers never index outside the roster.
         for (gi, game) in round.games.iter().enumerate() {
-            if !game.is_well_formed() {
-                violations.push(Violation::Malformed { round: ri, game: gi });
-            }
-            if !in_range(game) {
-                // Report each offending player; skip ledger updates for safety.
-                for who in game.players() {
-                    let bad = match who {
-                        Player::M(m) => m.0 >= roster.men,
-                        Player::W(w) => w.0 >= roster.women,
-                    };
-                    if bad {
-                        violations.push(Violation::OutOfRange { round: ri, game: gi, who });
-                    }
-                }
+            if !in_range(game, roster) {
                 continue;
             }
-
-            // Occupancy within the round.
-            for who in game.players() {
-                if seen.insert(who, ()).is_some() {
-                    violations.push(Violation::DoubleBooked { round: ri, who });
+            active += 4;
+            let c = round.court(gi) as usize;
+            if c < courts as usize {
+                for m in [game.a.man, game.b.man] {
+                    courts_per_man[m.0 as usize][c] += 1;
+                }
+                for w in [game.a.woman, game.b.woman] {
+                    courts_per_woman[w.0 as usize][c] += 1;
                 }
-                active += 1;
-            }
-
-            // Ledgers.
-            for (m, w) in game.partnerships() {
-                *partner_counts.entry((m.0, w.0)).or_insert(0) += 1;
-            }
-            for (m, w) in game.mixed_opps() {
-                *mixed_counts.entry((m.0, w.0)).or_insert(0) += 1;
             }
             let mp = game.man_pair();
             *man_counts.entry((mp.0 .0, mp.1 .0)).or_insert(0) += 1;
@@ -196,40 +419,37 @@ pub fn verify(schedule: &Schedule, roster: Roster, courts: u16) -> Report {
 
         byes_per_round.push(roster.total_players().saturating_sub(active));
     }
+    let side = SideTally::of(schedule, roster);
+    let (met_per_man, met_per_woman) = people_met(schedule, roster);
 
-    // Hard-ledger violations.
-    for (&(m, w), &c) in &partner_counts {
-        if c > 1 {
-            violations.push(Violation::RepeatedPartnership { man: m, woman: w, count: c });
-        }
-    }
-    for (&(m, w), &c) in &mixed_counts {
-        if c > 1 {
-            violations.push(Violation::RepeatedMixedOpp { man: m, woman: w, count: c });
-        }
-    }
+    let partner_at = pair_slots(schedule, roster, Game::partnerships);
+    let mixed_at = pair_slots(schedule, roster, Game::mixed_opps);
 
-    let (man_repeat_excess, man_max_meetings) = repeat_stats(&man_counts);
-    let (woman_repeat_excess, woman_max_meetings) = repeat_stats(&woman_counts);
-    let (partner_repeat_excess, _) = repeat_stats(&partner_counts);
-    let (mixed_repeat_excess, _) = repeat_stats(&mixed_counts);
+    let (man_repeat_excess, man_max_meetings) = repeat_stats(man_counts.values().copied());
+    let (woman_repeat_excess, woman_max_meetings) = repeat_stats(woman_counts.values().copied());
+    let (partner_repeat_excess, _) = repeat_stats(partner_at.values().map(Vec::len));
+    let (mixed_repeat_excess, _) = repeat_stats(mixed_at.values().map(Vec::len));
 
     let games = schedule.num_games();
     let rounds = schedule.num_rounds();
-    let court_slots = rounds * courts as usize;
+    let all_games: Vec<Game> = schedule.all_games().copied().collect();
+    let round_floor = round_floor(&all_games, roster, &court_plan);
+    let court_slots = court_plan.slots(rounds);
     let court_utilization = if court_slots == 0 {
         0.0
     } else {
         games as f64 / court_slots as f64
     };
 
-    Report {
+    let mut report = Report {
         roster,
         courts,
-        violations,
+        court_plan,
+        violations: Vec::new(),
         games,
         max_games: roster.max_games(),
         rounds,
+        round_floor,
         partner_repeat_excess,
         partner_repeat_floor: roster.min_partner_repeats(games),
         mixed_repeat_excess,
@@ -242,7 +462,507 @@ pub fn verify(schedule: &Schedule, roster: Roster, courts: u16) -> Report {
         woman_max_meetings,
         games_per_man,
         games_per_woman,
+        met_per_man,
+        met_per_woman,
+        courts_per_man,
+        courts_per_woman,
         byes_per_round,
         court_utilization,
+        side_games: side.games,
+        side_games_per_man: side.per_man,
+        side_games_per_woman: side.per_woman,
+        side_repeat_excess: side.repeat_excess,
+        drill_slots: side.drill_slots,
+        rule_scores: Vec::new(),
+        optimal: false,
+    };
+    for rule in rules {
+        rule.apply(schedule, &mut report);
+    }
+    report.optimal = report.meets_bounds();
+    report
+}
+
+/// Distinct players each man and each woman partners or faces in
+/// `schedule`'s games. Games off the roster are skipped.
+fn people_met(schedule: &Schedule, roster: Roster) -> (Vec<usize>, Vec<usize>) {
+    let mut pairs: HashSet<(Player, Player)> = HashSet::new();
+    for game in schedule.all_games().filter(|g| in_range(g, roster)) {
+        let players = game.players();
+        for (i, &p) in players.iter().enumerate() {
+            for &q in &players[i + 1..] {
+                pairs.insert((p.min(q), p.max(q)));
+            }
+        }
+    }
+    let mut men = vec![0usize; roster.men as usize];
+    let mut women = vec![0usize; roster.women as usize];
+    for p in pairs.into_iter().flat_map(|(p, q)| [p, q]) {
+        match p {
+            Player::M(m) => men[m.0 as usize] += 1,
+            Player::W(w) => women[w.0 as usize] += 1,
+        }
+    }
+    (men, women)
+}
+
+/// The side-game measurements of a schedule. Players off the roster are the
+/// structure rule's to report and are skipped here.
+struct SideTally {
+    games: usize,
+    per_man: Vec<usize>,
+    per_woman: Vec<usize>,
+    repeat_excess: usize,
+    drill_slots: usize,
+}
+
+impl SideTally {
+    fn of(schedule: &Schedule, roster: Roster) -> Self {
+        let mut tally = SideTally {
+            games: 0,
+            per_man: vec![0; roster.men as usize],
+            per_woman: vec![0; roster.women as usize],
+            repeat_excess: 0,
+            drill_slots: 0,
+        };
+        let mut met: HashMap<(Player, Player), usize> = HashMap::new();
+        for side in schedule.rounds.iter().flat_map(|r| r.side.iter()) {
+            let players = side.players();
+            if let SideGame::Drill { .. } = side {
+                tally.drill_slots += players.len();
+                continue;
+            }
+            tally.games += 1;
+            let players: Vec<Player> = players.into_iter().filter(|&p| on_roster(p, roster)).collect();
+            for (i, &p) in players.iter().enumerate() {
+                match p {
+                    Player::M(m) => tally.per_man[m.0 as usize] += 1,
+                    Player::W(w) => tally.per_woman[w.0 as usize] += 1,
+                }
+                for &q in &players[i + 1..] {
+                    *met.entry((p.min(q), p.max(q))).or_insert(0) += 1;
+                }
+            }
+        }
+        tally.repeat_excess = repeat_stats(met.values().copied()).0;
+        tally
+    }
+}
+
+/// The counting floor on rounds for `games` under `plan`: the uniform bound
+/// at the plan's most courts, or the rounds the plan needs to offer a court
+/// per game, whichever is higher.
+fn round_floor(games: &[Game], roster: Roster, plan: &CourtPlan) -> usize {
+    let uniform = pack::lower_bound(games, roster, plan.most()).rounds();
+    uniform.max(plan.rounds_for(roster, games.len()).unwrap_or(0))
+}
+
+/// One ledger's counts over a dense key space, plus a histogram of counts so
+/// the repeat excess, the largest count and the smallest count all update in
+/// amortized O(1) as entries are added and removed. Removing a key whose
+/// count is already zero is a caller bug and panics.
+#[derive(Clone, Debug)]
+struct Tally {
+    count: Vec<u32>,
+    /// `hist[c]` = number of keys whose count is `c` (keys at zero included).
+    hist: Vec<usize>,
+    excess: usize,
+    max: usize,
+    min: usize,
+}
+
+impl Tally {
+    fn new(keys: usize) -> Self {
+        Tally { count: vec![0; keys], hist: vec![keys], excess: 0, max: 0, min: 0 }
+    }
+
+    fn get(&self, k: usize) -> usize {
+        self.count[k] as usize
+    }
+
+    fn add(&mut self, k: usize) {
+        let c = self.count[k] as usize;
+        self.count[k] += 1;
+        if self.hist.len() <= c + 1 {
+            self.hist.push(0);
+        }
+        self.hist[c] -= 1;
+        self.hist[c + 1] += 1;
+        if c >= 1 {
+            self.excess += 1;
+        }
+        self.max = self.max.max(c + 1);
+        if c == self.min && self.hist[c] == 0 {
+            self.min += 1;
+        }
+    }
+
+    /// Take one off `k`'s count. Panics if the count is zero.
+    fn remove(&mut self, k: usize) {
+        let c = self.count[k] as usize;
+        assert!(c > 0, "LiveReport: removing an entry that was never added");
+        self.count[k] -= 1;
+        self.hist[c] -= 1;
+        self.hist[c - 1] += 1;
+        if c >= 2 {
+            self.excess -= 1;
+        }
+        while self.max > 0 && self.hist[self.max] == 0 {
+            self.max -= 1;
+        }
+        self.min = self.min.min(c - 1);
+    }
+}
+
+/// An incrementally maintained quality report: the aggregate numbers of
+/// [`verify`], updated in amortized O(1) per game as games and rounds are
+/// added or removed. Creating or cloning one costs O(M·W) for the per-man
+/// bitsets kept for the builders' scans.
+///
+/// This is the ledger every optimizer searches with, so the numbers they
+/// minimize are the oracle's numbers by construction. It assumes each game is
+/// well-formed and in range (the builders only ever produce such games) and
+/// does not track per-round byes or violation locations — run [`verify`] on
+/// the finished schedule for those.
+#[derive(Clone, Debug)]
+pub struct LiveReport {
+    roster: Roster,
+    courts: CourtPlan,
+    games: usize,
+    rounds: usize,
+    partner: Tally,
+    mixed: Tally,
+    man: Tally,
+    woman: Tally,
+    /// Games per player, men first then women.
+    played: Tally,
+    /// Per man, the women he has never partnered / never faced: bitset views
+    /// of the two hard ledgers for the builders' candidate scans.
+    unpartnered: Vec<Bits>,
+    unopposed: Vec<Bits>,
+    /// The earlier nights, when this is one night of a season.
+    history: Option<Arc<History>>,
+    /// Times this night's partnerships and mixed oppositions were played on
+    /// earlier nights, summed over its games.
+    stale: usize,
+    /// Times this night's man–man and woman–woman pairs met on earlier
+    /// nights, summed over its games.
+    stale_met: usize,
+}
+
+/// A [`Season`]'s ledgers as a night's [`LiveReport`] reads them.
+#[derive(Debug)]
+struct History {
+    season: Season,
+    /// Per man, the women he partnered / faced on no earlier night.
+    unpartnered: Vec<Bits>,
+    unopposed: Vec<Bits>,
+}
+
+impl LiveReport {
+    /// An empty schedule.
+    pub fn new(roster: Roster, courts: impl Into<CourtPlan>) -> Self {
+        let courts = courts.into();
+        let (m, w) = (roster.men as usize, roster.women as usize);
+        LiveReport {
+            roster,
+            courts,
+            games: 0,
+            rounds: 0,
+            partner: Tally::new(m * w),
+            mixed: Tally::new(m * w),
+            man: Tally::new(m * m),
+            woman: Tally::new(w * w),
+            played: Tally::new(m + w),
+            unpartnered: vec![Bits::full(w); m],
+            unopposed: vec![Bits::full(w); m],
+            history: None,
+            stale: 0,
+            stale_met: 0,
+        }
+    }
+
+    /// An empty night of `season`: the night's own ledgers start empty, and
+    /// [`LiveReport::season_repeats`] and [`LiveReport::season_meetings`]
+    /// count what its games repeat from the nights recorded so far.
+    pub fn in_season(season: &Season, courts: impl Into<CourtPlan>) -> Self {
+        let roster = season.roster();
+        let fresh = |before: fn(&Season, Man, Woman) -> usize| -> Vec<Bits> {
+            let row = |m: Man| {
+                let mut bits = Bits::new(roster.women as usize);
+                for w in roster.women_iter().filter(|&w| before(season, m, w) == 0) {
+                    bits.insert(w.0 as usize);
+                }
+                bits
+            };
+            roster.men_iter().map(row).collect()
+        };
+        let history = History {
+            season: season.clone(),
+            unpartnered: fresh(Season::partnered),
+            unopposed: fresh(Season::opposed),
+        };
+        LiveReport { history: Some(Arc::new(history)), ..LiveReport::new(roster, courts) }
+    }
+
+    /// The live report of an existing schedule.
+    pub fn of(schedule: &Schedule, roster: Roster, courts: impl Into<CourtPlan>) -> Self {
+        let mut live = LiveReport::new(roster, courts);
+        for round in &schedule.rounds {
+            live.add_round(&round.games);
+        }
+        live
+    }
+
+    /// Index into both `(man, woman)` ledgers.
+    fn pair_key(&self, m: Man, w: Woman) -> usize {
+        m.0 as usize * self.roster.women as usize + w.0 as usize
+    }
+
+    fn man_key(&self, a: Man, b: Man) -> usize {
+        let (x, y) = if a <= b { (a, b) } else { (b, a) };
+        x.0 as usize * self.roster.men as usize + y.0 as usize
+    }
+
+    fn woman_key(&self, a: Woman, b: Woman) -> usize {
+        let (x, y) = if a <= b { (a, b) } else { (b, a) };
+        x.0 as usize * self.roster.women as usize + y.0 as usize
+    }
+
+    fn player_key(&self, p: Player) -> usize {
+        self.roster.seat(p)
+    }
+
+    /// Open a new, empty round; later [`LiveReport::add_game`] calls fill it.
+    pub fn new_round(&mut self) {
+        self.rounds += 1;
+    }
+
+    /// Record one game in the current round.
+    pub fn add_game(&mut self, g: &Game) {
+        debug_assert!(g.is_well_formed(), "LiveReport: malformed game {}", g);
+        self.games += 1;
+        for (m, w) in g.partnerships() {
+            self.partner.add(self.pair_key(m, w));
+            self.unpartnered[m.0 as usize].remove(w.0 as usize);
+        }
+        for (m, w) in g.mixed_opps() {
+            self.mixed.add(self.pair_key(m, w));
+            self.unopposed[m.0 as usize].remove(w.0 as usize);
+        }
+        self.man.add(self.man_key(g.a.man, g.b.man));
+        self.woman.add(self.woman_key(g.a.woman, g.b.woman));
+        for p in g.players() {
+            self.played.add(self.player_key(p));
+        }
+        self.stale += self.repeated_before(g);
+        self.stale_met += self.met_before(g);
+    }
+
+    /// Undo [`LiveReport::add_game`]. Panics if the game was never added.
+    pub fn remove_game(&mut self, g: &Game) {
+        self.games -= 1;
+        for (m, w) in g.partnerships() {
+            self.partner.remove(self.pair_key(m, w));
+            if self.partnered(m, w) == 0 {
+                self.unpartnered[m.0 as usize].insert(w.0 as usize);
+            }
+        }
+        for (m, w) in g.mixed_opps() {
+            self.mixed.remove(self.pair_key(m, w));
+            if self.opposed(m, w) == 0 {
+                self.unopposed[m.0 as usize].insert(w.0 as usize);
+            }
+        }
+        self.man.remove(self.man_key(g.a.man, g.b.man));
+        self.woman.remove(self.woman_key(g.a.woman, g.b.woman));
+        for p in g.players() {
+            self.played.remove(self.player_key(p));
+        }
+        self.stale -= self.repeated_before(g);
+        self.stale_met -= self.met_before(g);
+    }
+
+    /// Record a whole round of games.
+    pub fn add_round(&mut self, games: &[Game]) {
+        self.new_round();
+        for g in games {
+            self.add_game(g);
+        }
+    }
+
+    /// Undo [`LiveReport::add_round`] (or a [`LiveReport::new_round`] plus
+    /// its games).
+    pub fn remove_round(&mut self, games: &[Game]) {
+        self.rounds -= 1;
+        for g in games {
+            self.remove_game(g);
+        }
+    }
+
+    pub fn roster(&self) -> Roster {
+        self.roster
+    }
+
+    pub fn games(&self) -> usize {
+        self.games
+    }
+
+    pub fn rounds(&self) -> usize {
+        self.rounds
+    }
+
+    /// Times `(m, w)` have partnered so far.
+    pub fn partnered(&self, m: Man, w: Woman) -> usize {
+        self.partner.get(self.pair_key(m, w))
+    }
+
+    /// Times `m` has faced `w` across the net so far.
+    pub fn opposed(&self, m: Man, w: Woman) -> usize {
+        self.mixed.get(self.pair_key(m, w))
+    }
+
+    /// The women `m` has never partnered, by index.
+    pub fn unpartnered(&self, m: Man) -> &Bits {
+        &self.unpartnered[m.0 as usize]
+    }
+
+    /// The women `m` has never faced across the net, by index.
+    pub fn unopposed(&self, m: Man) -> &Bits {
+        &self.unopposed[m.0 as usize]
+    }
+
+    /// Times men `a` and `b` have faced each other so far.
+    pub fn men_met(&self, a: Man, b: Man) -> usize {
+        self.man.get(self.man_key(a, b))
+    }
+
+    /// Times women `a` and `b` have faced each other so far.
+    pub fn women_met(&self, a: Woman, b: Woman) -> usize {
+        self.woman.get(self.woman_key(a, b))
+    }
+
+    /// Distinct players `p` has partnered or faced so far, counted in
+    /// O(M + W).
+    pub fn people_met(&self, p: Player) -> usize {
+        let roster = self.roster;
+        let met = |m: Man, w: Woman| self.partnered(m, w) + self.opposed(m, w) > 0;
+        match p {
+            Player::M(m) => {
+                let women = roster.women_iter().filter(|&w| met(m, w)).count();
+                women + roster.men_iter().filter(|&b| b != m && self.men_met(m, b) > 0).count()
+            }
+            Player::W(w) => {
+                let men = roster.men_iter().filter(|&m| met(m, w)).count();
+                men + roster.women_iter().filter(|&x| x != w && self.women_met(w, x) > 0).count()
+            }
+        }
+    }
+
+    /// The season this is one night of, if any.
+    pub fn season(&self) -> Option<&Season> {
+        self.history.as_ref().map(|h| &h.season)
+    }
+
+    /// Times `(m, w)` partnered on earlier nights of the season (0 outside
+    /// one).
+    pub fn partnered_before(&self, m: Man, w: Woman) -> usize {
+        self.history.as_ref().map_or(0, |h| h.season.partnered(m, w))
+    }
+
+    /// Times `m` faced `w` on earlier nights of the season.
+    pub fn opposed_before(&self, m: Man, w: Woman) -> usize {
+        self.history.as_ref().map_or(0, |h| h.season.opposed(m, w))
+    }
+
+    /// Times men `a` and `b` met on earlier nights of the season.
+    pub fn men_met_before(&self, a: Man, b: Man) -> usize {
+        self.history.as_ref().map_or(0, |h| h.season.men_met(a, b))
+    }
+
+    /// Times women `a` and `b` met on earlier nights of the season.
+    pub fn women_met_before(&self, a: Woman, b: Woman) -> usize {
+        self.history.as_ref().map_or(0, |h| h.season.women_met(a, b))
+    }
+
+    /// The women `m` partnered and faced on no earlier night, by index;
+    /// `None` outside a season.
+    pub fn fresh_before(&self, m: Man) -> Option<(&Bits, &Bits)> {
+        let h = self.history.as_deref()?;
+        Some((&h.unpartnered[m.0 as usize], &h.unopposed[m.0 as usize]))
+    }
+
+    /// Times `g`'s partnerships and mixed oppositions were played on earlier
+    /// nights.
+    pub fn repeated_before(&self, g: &Game) -> usize {
+        let partners = g.partnerships().into_iter().map(|(m, w)| self.partnered_before(m, w));
+        let opponents = g.mixed_opps().into_iter().map(|(m, w)| self.opposed_before(m, w));
+        partners.chain(opponents).sum()
+    }
+
+    /// Times `g`'s man–man and woman–woman pairs met on earlier nights.
+    pub fn met_before(&self, g: &Game) -> usize {
+        self.men_met_before(g.a.man, g.b.man) + self.women_met_before(g.a.woman, g.b.woman)
+    }
+
+    /// Games `p` has played so far.
+    pub fn played(&self, p: Player) -> usize {
+        self.played.get(self.player_key(p))
+    }
+
+    /// No partnership or mixed opposition used twice (the Part 1 bar).
+    pub fn is_legal(&self) -> bool {
+        self.partner.excess == 0 && self.mixed.excess == 0
+    }
+
+    pub fn partner_repeat_excess(&self) -> usize {
+        self.partner.excess
+    }
+
+    pub fn mixed_repeat_excess(&self) -> usize {
+        self.mixed.excess
+    }
+
+    pub fn man_repeat_excess(&self) -> usize {
+        self.man.excess
+    }
+
+    pub fn man_max_meetings(&self) -> usize {
+        self.man.max
+    }
+
+    pub fn woman_repeat_excess(&self) -> usize {
+        self.woman.excess
+    }
+
+    pub fn woman_max_meetings(&self) -> usize {
+        self.woman.max
+    }
+
+    /// Times this night's partnerships and mixed oppositions were already
+    /// played on earlier nights of the season (0 outside one).
+    pub fn season_repeats(&self) -> usize {
+        self.stale
+    }
+
+    /// Times this night's man–man and woman–woman pairs already met on
+    /// earlier nights of the season.
+    pub fn season_meetings(&self) -> usize {
+        self.stale_met
+    }
+
+    /// Same as [`Report::participation_spread`].
+    pub fn participation_spread(&self) -> usize {
+        self.played.max - self.played.min
+    }
+
+    /// Same as [`Report::court_utilization`].
+    pub fn court_utilization(&self) -> f64 {
+        let slots = self.courts.slots(self.rounds);
+        if slots == 0 {
+            0.0
+        } else {
+            self.games as f64 / slots as f64
+        }
     }
 }
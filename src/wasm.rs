@@ -5,42 +5,207 @@
 //! a JSON string the web page renders. The serialization lives in the plain
 //! [`generate_json`] function (unit-tested on the host); the `#[wasm_bindgen]`
 //! entry point is a one-line shim over it, compiled only under the `wasm`
-//! feature.
+//! feature. [`Job`] is the stepwise variant the page drives between frames, so
+//! it can show improving results and stop on request.
 
-use crate::model::{Roster, Schedule};
-use crate::search::{optimize, EMPHASIS_BALANCED, EMPHASIS_COURTS, EMPHASIS_VARIETY};
-use crate::target::{by_games_per_player, by_total_games};
+use crate::budget::Budget;
+use crate::duty::assign_officials;
+use crate::model::{Player, Roster, Schedule};
+use crate::objective::Objective;
+use crate::rotation::assign_courts;
+use crate::search::{optimize_frontier, optimize_within, Optimizer, Progress};
+use crate::target::{by_games_per_player, by_rounds, by_total_games};
 use crate::verify::{verify, Report};
 
-/// Guard against inputs that would freeze the browser (the heuristic is
-/// `O(M²W²)` per court). Realistic pickleball sessions are well within this.
-const MAX_SIDE: u16 = 30;
+/// Guard against inputs that would freeze the browser. The builders' bitset
+/// scans keep 40 a side (charity-event size) responsive.
+const MAX_SIDE: u16 = 40;
 
 /// Modes: `0` courts, `1` balanced, `2` variety (Part 1); `3` each=param,
-/// `4` total=param (Part 2). `param` is ignored for Part 1.
-pub fn generate_json(men: u16, women: u16, courts: u16, mode: u8, param: u32, seed: u32) -> String {
-    if men > MAX_SIDE || women > MAX_SIDE {
-        return format!(
-            "{{\"error\":\"Too many players — cap each side at {} for the browser.\"}}",
-            MAX_SIDE
-        );
+/// `4` total=param, `6` rounds=param (Part 2); `5` the Part 1 trade-off menu, as
+/// `{"options":[…]}` with one schedule object per Pareto option, courts end
+/// first. `param` is ignored for Part 1. A non-zero
+/// `budget_ms` caps Part 1's search time, so large rosters still answer
+/// promptly; `0` means no cap. `weights` overrides objective weights on top
+/// of the mode's preset, e.g. `"excess:20, fairness:500"` (see
+/// [`Objective::with_overrides`]); empty keeps the preset.
+#[allow(clippy::too_many_arguments)]
+pub fn generate_json(
+    men: u16,
+    women: u16,
+    courts: u16,
+    mode: u8,
+    param: u32,
+    seed: u32,
+    budget_ms: u32,
+    weights: &str,
+) -> String {
+    if let Some(err) = too_many(men, women) {
+        return err;
     }
+    let obj = match objective(mode, weights) {
+        Ok(obj) => obj,
+        Err(err) => return error_json(&err),
+    };
     let roster = Roster::new(men, women);
     let courts = courts.max(1);
     let seed = seed as u64;
-    let iters = 20_000u32;
+    let iters = Some(ITERS);
+    let budget = match budget_ms {
+        0 => Budget::unlimited(),
+        ms => Budget::within(std::time::Duration::from_millis(ms as u64)),
+    };
 
+    if mode == FRONTIER {
+        let options = optimize_frontier(roster, courts, iters, &budget, &obj, seed);
+        return frontier_json(&options, roster, courts);
+    }
     let sched = match mode {
-        0 => optimize(roster, courts, iters, EMPHASIS_COURTS, seed),
-        2 => optimize(roster, courts, iters, EMPHASIS_VARIETY, seed),
-        3 => by_games_per_player(roster, courts, param, seed),
-        4 => by_total_games(roster, courts, param as usize, seed),
-        _ => optimize(roster, courts, iters, EMPHASIS_BALANCED, seed),
+        3 => by_games_per_player(roster, courts, param, &obj, seed),
+        4 => by_total_games(roster, courts, param as usize, &obj, seed),
+        6 => by_rounds(roster, courts, param as usize, &obj, seed),
+        _ => optimize_within(roster, courts, iters, &budget, &obj, seed),
+    };
+    finish(&sched, roster, courts)
+}
+
+/// The mode's emphasis preset with `weights` applied.
+fn objective(mode: u8, weights: &str) -> Result<Objective, String> {
+    let preset = match mode {
+        0 => Objective::courts(),
+        2 => Objective::variety(),
+        _ => Objective::balanced(),
     };
+    preset.with_overrides(weights)
+}
+
+fn error_json(message: &str) -> String {
+    format!("{{\"error\":\"{}\"}}", message.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// The trade-off menu mode.
+const FRONTIER: u8 = 5;
+
+/// `{"options":[…]}`, each option serialized like a single schedule.
+fn frontier_json(options: &[(Schedule, Report)], roster: Roster, courts: u16) -> String {
+    let parts: Vec<String> = options.iter().map(|(s, _)| finish(s, roster, courts)).collect();
+    format!("{{\"options\":[{}]}}", parts.join(","))
+}
+
+/// Part 1 step count for the browser.
+const ITERS: u32 = 20_000;
+
+/// The error JSON for a roster over [`MAX_SIDE`], if it is.
+fn too_many(men: u16, women: u16) -> Option<String> {
+    (men > MAX_SIDE || women > MAX_SIDE).then(|| {
+        error_json(&format!("Too many players — cap each side at {} for the browser.", MAX_SIDE))
+    })
+}
+
+/// Rotate courts, assign officials from the byes, verify and serialize.
+fn finish(sched: &Schedule, roster: Roster, courts: u16) -> String {
+    let sched = assign_officials(&assign_courts(sched, roster, courts), roster);
     let report = verify(&sched, roster, courts);
     to_json(&sched, &report)
 }
 
+/// A generation the page advances a slice at a time. Part 1 modes run an
+/// [`Optimizer`]; Part 2 modes (and errors) are fast and finish on creation.
+pub struct Job {
+    roster: Roster,
+    courts: u16,
+    optimizer: Option<Optimizer>,
+    /// Report the trade-off menu rather than one pick.
+    frontier: bool,
+    /// The result, once known without stepping.
+    ready: Option<String>,
+}
+
+impl Job {
+    /// Same arguments as [`generate_json`], minus the budget — the caller
+    /// decides how long to keep stepping.
+    pub fn new(
+        men: u16,
+        women: u16,
+        courts: u16,
+        mode: u8,
+        param: u32,
+        seed: u32,
+        weights: &str,
+    ) -> Job {
+        let roster = Roster::new(men, women);
+        let courts = courts.max(1);
+        let seed = seed as u64;
+        let frontier = mode == FRONTIER;
+        let mut job = Job { roster, courts, optimizer: None, frontier, ready: None };
+        if let Some(err) = too_many(men, women) {
+            job.ready = Some(err);
+            return job;
+        }
+        let obj = match objective(mode, weights) {
+            Ok(obj) => obj,
+            Err(err) => {
+                job.ready = Some(error_json(&err));
+                return job;
+            }
+        };
+        let sched = match mode {
+            3 => by_games_per_player(roster, courts, param, &obj, seed),
+            4 => by_total_games(roster, courts, param as usize, &obj, seed),
+            6 => by_rounds(roster, courts, param as usize, &obj, seed),
+            _ => {
+                job.optimizer = Some(Optimizer::new(roster, courts, ITERS, &obj, seed));
+                return job;
+            }
+        };
+        job.ready = Some(finish(&sched, roster, courts));
+        job
+    }
+
+    /// Work for about `ms` milliseconds; returns whether the job is finished.
+    pub fn step(&mut self, ms: u32) -> bool {
+        match &mut self.optimizer {
+            Some(opt) => opt.step(&Budget::within(std::time::Duration::from_millis(ms as u64))),
+            None => true,
+        }
+    }
+
+    /// The best schedule so far as [`generate_json`]-style JSON.
+    pub fn result_json(&self) -> String {
+        match (&self.ready, &self.optimizer) {
+            (Some(json), _) => json.clone(),
+            (None, Some(opt)) if self.frontier => {
+                frontier_json(&opt.frontier(), self.roster, self.courts)
+            }
+            (None, Some(opt)) => finish(&opt.best(), self.roster, self.courts),
+            (None, None) => unreachable!("a job has a result or an optimizer"),
+        }
+    }
+
+    /// The latest [`Progress`] as JSON, or `null` before any start has run.
+    pub fn progress_json(&self) -> String {
+        match self.optimizer.as_ref().and_then(Optimizer::progress) {
+            Some(p) => progress_to_json(&p),
+            None => "null".to_string(),
+        }
+    }
+}
+
+fn progress_to_json(p: &Progress) -> String {
+    format!(
+        "{{\"start\":{},\"starts\":{},\"step\":{},\"steps\":{},\"games\":{},\"rounds\":{},\"manExcess\":{},\"womanExcess\":{},\"temperature\":{:.3}}}",
+        p.start,
+        p.starts,
+        p.step,
+        p.steps.map_or("null".to_string(), |n| n.to_string()),
+        p.games,
+        p.rounds,
+        p.man_excess,
+        p.woman_excess,
+        p.temperature
+    )
+}
+
 fn json_usize_arr(v: &[usize]) -> String {
     let mut s = String::from("[");
     for (i, x) in v.iter().enumerate() {
@@ -61,7 +226,8 @@ fn to_json(sched: &Schedule, r: &Report) -> String {
         r.roster.men, r.roster.women, r.courts
     ));
 
-    // rounds: [[ [ma,wa,mb,wb], ... ], ...]  (0-indexed)
+    // rounds: [[ [ma,wa,mb,wb,court,official], ... ], ...]  (0-indexed; the
+    // official is null, {"man":i} or {"woman":i})
     out.push_str("\"rounds\":[");
     for (ri, round) in sched.rounds.iter().enumerate() {
         if ri > 0 {
@@ -72,9 +238,19 @@ fn to_json(sched: &Schedule, r: &Report) -> String {
             if gi > 0 {
                 out.push(',');
             }
+            let official = match round.official(gi) {
+                Some(Player::M(m)) => format!("{{\"man\":{}}}", m.0),
+                Some(Player::W(w)) => format!("{{\"woman\":{}}}", w.0),
+                None => "null".to_string(),
+            };
             out.push_str(&format!(
-                "[{},{},{},{}]",
-                g.a.man.0, g.a.woman.0, g.b.man.0, g.b.woman.0
+                "[{},{},{},{},{},{}]",
+                g.a.man.0,
+                g.a.woman.0,
+                g.b.man.0,
+                g.b.woman.0,
+                round.court(gi),
+                official
             ));
         }
         out.push(']');
@@ -83,13 +259,14 @@ fn to_json(sched: &Schedule, r: &Report) -> String {
 
     out.push_str("\"report\":{");
     out.push_str(&format!(
-        "\"games\":{},\"maxGames\":{},\"rounds\":{},",
-        r.games, r.max_games, r.rounds
+        "\"games\":{},\"maxGames\":{},\"rounds\":{},\"roundFloor\":{},",
+        r.games, r.max_games, r.rounds, r.round_floor
     ));
     out.push_str(&format!(
-        "\"legal\":{},\"structurallyValid\":{},",
+        "\"legal\":{},\"structurallyValid\":{},\"optimal\":{},",
         r.is_legal(),
-        r.is_structurally_valid()
+        r.is_structurally_valid(),
+        r.optimal
     ));
     out.push_str(&format!(
         "\"partnerExcess\":{},\"partnerFloor\":{},",
@@ -108,9 +285,10 @@ fn to_json(sched: &Schedule, r: &Report) -> String {
         r.woman_repeat_excess, r.woman_repeat_floor, r.woman_max_meetings
     ));
     out.push_str(&format!(
-        "\"courtUtil\":{:.4},\"spread\":{},",
+        "\"courtUtil\":{:.4},\"spread\":{},\"courtImbalance\":{},",
         r.court_utilization,
-        r.participation_spread()
+        r.participation_spread(),
+        r.court_imbalance()
     ));
     out.push_str(&format!(
         "\"gamesPerMan\":{},\"gamesPerWoman\":{}",
@@ -129,7 +307,51 @@ mod bindings {
 
     /// Generate a schedule and return it as a JSON string.
     #[wasm_bindgen]
-    pub fn generate(men: u16, women: u16, courts: u16, mode: u8, param: u32, seed: u32) -> String {
-        super::generate_json(men, women, courts, mode, param, seed)
+    #[allow(clippy::too_many_arguments)]
+    pub fn generate(
+        men: u16,
+        women: u16,
+        courts: u16,
+        mode: u8,
+        param: u32,
+        seed: u32,
+        budget_ms: u32,
+        weights: &str,
+    ) -> String {
+        super::generate_json(men, women, courts, mode, param, seed, budget_ms, weights)
+    }
+
+    /// A stepwise generation: `new Job(...)`, then `step(ms)` until it returns
+    /// true (or the user stops), reading `progress()` and `result()` between
+    /// steps.
+    #[wasm_bindgen(js_name = Job)]
+    pub struct JobHandle(super::Job);
+
+    #[wasm_bindgen(js_class = Job)]
+    impl JobHandle {
+        #[wasm_bindgen(constructor)]
+        pub fn new(
+            men: u16,
+            women: u16,
+            courts: u16,
+            mode: u8,
+            param: u32,
+            seed: u32,
+            weights: &str,
+        ) -> JobHandle {
+            JobHandle(super::Job::new(men, women, courts, mode, param, seed, weights))
+        }
+
+        pub fn step(&mut self, ms: u32) -> bool {
+            self.0.step(ms)
+        }
+
+        pub fn progress(&self) -> String {
+            self.0.progress_json()
+        }
+
+        pub fn result(&self) -> String {
+            self.0.result_json()
+        }
     }
 }
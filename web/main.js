@@ -1,12 +1,21 @@
-import init, { generate } from "./pkg/pickleball_scheduler.js";
+import init, { Job } from "./pkg/pickleball_scheduler.js";
 
 const $ = (sel) => document.querySelector(sel);
 const $$ = (sel) => Array.from(document.querySelectorAll(sel));
 
 let ready = false;
 let rosterMode = "counts"; // "counts" | "names"
-let goal = "part1"; // "part1" | "each" | "total"
+let goal = "part1"; // "part1" | "each" | "total" | "rounds"
 let seed = (Math.random() * 0xffffffff) >>> 0;
+// Longest the optimizer may search before handing back its best schedule.
+const BUDGET_MS = 2000;
+// Work per step between repaints, and how often the interim result is drawn.
+const SLICE_MS = 50;
+const REDRAW_MS = 250;
+let stopRequested = false;
+// The trade-off menu (emphasis "All trade-offs"): the options and which is shown.
+let options = null;
+let picked = 0;
 
 // ---- boot ----
 (async () => {
@@ -29,10 +38,10 @@ $$(".tab").forEach((t) =>
 );
 
 // ---- goal segmented ----
-$$(".seg").forEach((s) =>
+$$("#goal .seg").forEach((s) =>
   s.addEventListener("click", () => {
     goal = s.dataset.goal;
-    $$(".seg").forEach((x) => x.classList.toggle("active", x === s));
+    $$("#goal .seg").forEach((x) => x.classList.toggle("active", x === s));
     $$("[data-goal-pane]").forEach((p) => (p.hidden = p.dataset.goalPane !== goal));
   })
 );
@@ -48,6 +57,7 @@ $("#womenNames").addEventListener("input", updateNamesCount);
 
 // ---- actions ----
 $("#generate").addEventListener("click", () => run());
+$("#stop").addEventListener("click", () => (stopRequested = true));
 $("#reshuffle").addEventListener("click", () => {
   seed = (Math.random() * 0xffffffff) >>> 0;
   run();
@@ -74,8 +84,8 @@ function getRoster() {
     const women = parseNames($("#womenNames").value);
     return { men, women };
   }
-  const M = clampInt($("#men").value, 0, 30);
-  const W = clampInt($("#women").value, 0, 30);
+  const M = clampInt($("#men").value, 0, 40);
+  const W = clampInt($("#women").value, 0, 40);
   return {
     men: Array.from({ length: M }, (_, i) => `M${i + 1}`),
     women: Array.from({ length: W }, (_, i) => `W${i + 1}`),
@@ -85,6 +95,7 @@ function getRoster() {
 function getModeParam() {
   if (goal === "each") return { mode: 3, param: clampInt($("#eachN").value, 1, 60) };
   if (goal === "total") return { mode: 4, param: clampInt($("#totalG").value, 1, 400) };
+  if (goal === "rounds") return { mode: 6, param: clampInt($("#roundsR").value, 1, 60) };
   return { mode: parseInt($("#emphasis").value, 10), param: 0 };
 }
 
@@ -98,6 +109,7 @@ function showBanner(msg) {
 // ---- main run ----
 function run() {
   if (!ready) return showBanner("Engine still loading — one moment, then try again.");
+  if ($("#generate").disabled) return; // a generation is already running
 
   const { men, women } = getRoster();
   const courts = clampInt($("#courts").value, 1, 20);
@@ -110,26 +122,59 @@ function run() {
   const btn = $("#generate");
   btn.disabled = true;
   btn.textContent = "Generating…";
+  stopRequested = false;
+  picked = 0;
+  $("#stop").hidden = false;
 
-  // Let the browser paint the disabled state before the (synchronous) compute.
-  requestAnimationFrame(() => {
-    let data;
+  let job;
+  try {
+    job = new Job(men.length, women.length, courts, mode, param, seed, $("#weights").value);
+  } catch (e) {
+    console.error(e);
+    showBanner("Something went wrong generating the schedule.");
+    return finish(btn, null);
+  }
+  const stopAt = performance.now() + BUDGET_MS;
+  let lastDraw = 0;
+
+  // Step the engine a slice at a time so the page stays live: show the best
+  // schedule so far as it improves, and stop on request or at the budget.
+  const tick = () => {
+    let done, data;
     try {
-      const json = generate(men.length, women.length, courts, mode, param, seed);
-      data = JSON.parse(json);
+      done = job.step(SLICE_MS) || stopRequested || performance.now() >= stopAt;
+      const now = performance.now();
+      if (done || now - lastDraw >= REDRAW_MS) {
+        lastDraw = now;
+        data = JSON.parse(job.result());
+        showProgress(btn, JSON.parse(job.progress()));
+      }
     } catch (e) {
       console.error(e);
       showBanner("Something went wrong generating the schedule.");
-      resetBtn(btn);
-      return;
+      return finish(btn, job);
     }
-    if (data.error) {
+    if (data && data.error) {
       showBanner(data.error);
-    } else {
-      render(data, men, women);
+      return finish(btn, job);
     }
-    resetBtn(btn);
-  });
+    if (data) render(data, men, women, done);
+    if (done) return finish(btn, job);
+    setTimeout(tick, 0);
+  };
+  // Let the browser paint the disabled state before the first slice.
+  requestAnimationFrame(tick);
+}
+
+function showProgress(btn, p) {
+  if (!p) return;
+  btn.textContent = `Generating… ${p.games} games, ${p.rounds} rounds (run ${p.start + 1}/${p.starts})`;
+}
+
+function finish(btn, job) {
+  if (job) job.free();
+  $("#stop").hidden = true;
+  resetBtn(btn);
 }
 
 function resetBtn(btn) {
@@ -138,11 +183,36 @@ function resetBtn(btn) {
 }
 
 // ---- rendering ----
-function render(data, men, women) {
+function render(data, men, women, final = true) {
+  const first = $("#results").hidden;
   $("#results").hidden = false;
+  options = data.options || null;
+  $("#options").hidden = !options;
+  if (options) {
+    picked = Math.min(picked, options.length - 1);
+    renderOptions(men, women);
+    data = options[picked];
+  }
   renderStats(data.report);
   renderGrid(data, men, women);
-  $("#results").scrollIntoView({ behavior: "smooth", block: "start" });
+  // Interim redraws update in place; only scroll once per generation.
+  if (first || final) $("#results").scrollIntoView({ behavior: "smooth", block: "start" });
+}
+
+// One button per Pareto option; picking one redraws the schedule below.
+function renderOptions(men, women) {
+  const chips = options.map((o, i) => {
+    const r = o.report;
+    const label = `${r.rounds} rounds · ${r.manExcess + r.womanExcess} repeats`;
+    return `<button class="seg ${i === picked ? "active" : ""}" data-option="${i}">${label}</button>`;
+  });
+  $("#options").innerHTML = `<div class="segmented">${chips.join("")}</div>`;
+  $$("#options .seg").forEach((b) =>
+    b.addEventListener("click", () => {
+      picked = parseInt(b.dataset.option, 10);
+      render({ options }, men, women, false);
+    })
+  );
 }
 
 function tile(k, v, s, good, badge) {
@@ -165,8 +235,9 @@ function renderStats(r) {
   tiles.push(
     tile("Games", `${r.games}`, `of ${r.maxGames} possible`, r.games === r.maxGames, r.games === r.maxGames ? "max ✓" : "")
   );
+  const fewest = r.rounds === r.roundFloor;
   tiles.push(
-    tile("Court use", `${Math.round(r.courtUtil * 100)}%`, `${r.rounds} rounds`, r.courtUtil >= 0.999)
+    tile("Court use", `${Math.round(r.courtUtil * 100)}%`, `${r.rounds} rounds (floor ${r.roundFloor})`, r.courtUtil >= 0.999 || fewest, fewest ? "fewest ✓" : "")
   );
 
   // Partner/opponent repeats only matter once Part 2 pushes past the ceiling.
@@ -185,12 +256,26 @@ function renderStats(r) {
   tiles.push(
     tile("Games per player", lo === hi ? `${lo}` : `${lo}–${hi}`, lo === hi ? "everyone equal" : `spread ${hi - lo}`, lo === hi)
   );
+  tiles.push(
+    tile("Court rotation", `${r.courtImbalance}`, "most vs fewest games on a court, worst player", r.courtImbalance <= 1)
+  );
+
+  if (r.optimal) {
+    tiles.push(tile("Optimal", "Proven", "no legal schedule does better", true, "✓"));
+  }
 
   $("#stats").innerHTML = tiles.join("");
 }
 
+// The official line under a game, or nothing when no one officiates it.
+function officialSpan(official, men, women) {
+  if (!official) return "";
+  const name = "man" in official ? men[official.man] : women[official.woman];
+  return `<span class="ref">ref: ${esc(name)}</span>`;
+}
+
 function renderGrid(data, men, women) {
-  const cols = Math.max(1, ...data.rounds.map((r) => r.length));
+  const cols = Math.max(1, data.courts);
 
   let head = `<tr><th>Round</th>`;
   for (let c = 0; c < cols; c++) head += `<th>Court ${c + 1}</th>`;
@@ -200,17 +285,20 @@ function renderGrid(data, men, women) {
   data.rounds.forEach((round, ri) => {
     const activeMen = new Set();
     const activeWomen = new Set();
+    // Each game carries its court as a 5th element and its official, if any,
+    // as a 6th.
+    const byCourt = new Map(round.map((g) => [g[4], g]));
     let cells = "";
     for (let c = 0; c < cols; c++) {
-      const g = round[c];
+      const g = byCourt.get(c);
       if (!g) {
         cells += `<td class="empty">—</td>`;
         continue;
       }
-      const [ma, wa, mb, wb] = g;
+      const [ma, wa, mb, wb, , official] = g;
       activeMen.add(ma).add(mb);
       activeWomen.add(wa).add(wb);
-      cells += `<td class="game"><span class="a">${esc(men[ma])} &amp; ${esc(women[wa])}</span><span class="vs">vs</span><span class="b">${esc(men[mb])} &amp; ${esc(women[wb])}</span></td>`;
+      cells += `<td class="game"><span class="a">${esc(men[ma])} &amp; ${esc(women[wa])}</span><span class="vs">vs</span><span class="b">${esc(men[mb])} &amp; ${esc(women[wb])}</span>${officialSpan(official, men, women)}</td>`;
     }
     const byes = [];
     men.forEach((n, i) => { if (!activeMen.has(i)) byes.push(esc(n)); });